@@ -0,0 +1,69 @@
+//! Generates the solution registry of the main binary.
+//!
+//! With the `all` feature, every scaffolded puzzle in `src/bin/` (named `{year}-{day}.rs`) is
+//! compiled into the main binary as a module, so that `cargo all` can run all solutions in-process.
+//! Other commands are built without it, so that they do not depend on every solution compiling.
+//! The per-puzzle binaries are left untouched.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed=src/bin");
+
+    if env::var_os("CARGO_FEATURE_ALL").is_none() {
+        return;
+    }
+
+    let mut puzzles: Vec<(u16, u8, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?;
+                    let (year, day) = stem.split_once('-')?;
+                    let is_solution =
+                        path.extension()? == "rs" && year.len() == 4 && day.len() == 2;
+                    let (year, day) = (year.parse::<u16>().ok()?, day.parse::<u8>().ok()?);
+                    let is_solution = is_solution && year >= 2015 && (1..=25).contains(&day);
+                    is_solution.then(|| (year, day, path.to_str().unwrap().replace('\\', "/")))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort_unstable();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (year, day, path) in &puzzles {
+        modules.push_str(&format!(
+            "#[cfg(not(any(test, feature = \"dhat-heap\")))]\n\
+             #[allow(dead_code, clippy::all)]\n\
+             #[path = \"{path}\"]\n\
+             mod puzzle_{year}_{day:02};\n\n"
+        ));
+        entries.push_str(&format!("        puzzle_{year}_{day:02}::SOLUTION,\n"));
+    }
+
+    // NOTE: solutions are excluded from test builds, their tests already run as part of the
+    // per-puzzle binaries. They are also excluded from DHAT builds, which would otherwise
+    // register one global allocator per day.
+    let source = format!(
+        "{modules}\
+         #[cfg(not(any(test, feature = \"dhat-heap\")))]\n\
+         pub fn registry() -> advent_of_code::template::registry::Registry {{\n    \
+             advent_of_code::template::registry::Registry::new(vec![\n{entries}    ])\n\
+         }}\n\n\
+         #[cfg(any(test, feature = \"dhat-heap\"))]\n\
+         pub fn registry() -> advent_of_code::template::registry::Registry {{\n    \
+             advent_of_code::template::registry::Registry::default()\n\
+         }}\n"
+    );
+
+    fs::write(Path::new(&out_dir).join("solutions.rs"), source).unwrap();
+}
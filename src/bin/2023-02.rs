@@ -1,7 +1,7 @@
 use regex::Regex;
 use std::collections::HashMap;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 fn update_game_max_seen<'a>(line: &'a str, game_max_seen: &mut HashMap<&'a str, u32>) {
     let re = Regex::new(r"(\d+) (red|blue|green)").unwrap();
@@ -71,13 +71,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2286));
     }
 }
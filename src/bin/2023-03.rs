@@ -1,7 +1,7 @@
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2023, 3);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let width: usize = input.lines().next()?.len();
@@ -70,7 +70,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             acc
         })
         .values()
-        .filter(|&v| (v.len() == 2))
+        .filter(|&v| v.len() == 2)
         .map(|v| v.iter().product::<u32>())
         .sum();
 
@@ -83,13 +83,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(467835));
     }
 }
@@ -11,7 +11,7 @@ use nom::{
     Finish, IResult,
 };
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2023, 5);
 
 // Custom type to represent a range mapping
 #[derive(Debug)]
@@ -173,13 +173,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(46));
     }
 
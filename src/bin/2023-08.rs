@@ -13,7 +13,7 @@ use nom::{
 };
 use num::integer::lcm;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8);
 
 #[derive(Debug, PartialEq)]
 enum Direction {
@@ -65,7 +65,7 @@ fn parse_node(input: &str) -> IResult<&str, (&str, (&str, &str))> {
     Ok((i, (node_id, (left, right))))
 }
 
-fn parse_nodes(input: &str) -> IResult<&str, NodeMap> {
+fn parse_nodes(input: &str) -> IResult<&str, NodeMap<'_>> {
     let (i, nodes) = fold_many1(parse_node, HashMap::new, |mut acc: NodeMap, (n, lr)| {
         acc.insert(n, lr);
         acc
@@ -73,7 +73,7 @@ fn parse_nodes(input: &str) -> IResult<&str, NodeMap> {
     Ok((i, nodes))
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<Direction>, NodeMap)> {
+fn parse_input(input: &str) -> IResult<&str, (Vec<Direction>, NodeMap<'_>)> {
     let (i, (moves, nodes)) = separated_pair(parse_moves, multispace1, parse_nodes)(input)?;
     Ok((i, (moves, nodes)))
 }
@@ -147,13 +147,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 }
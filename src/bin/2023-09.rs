@@ -7,7 +7,7 @@ use nom::{
     sequence::terminated,
     IResult,
 };
-advent_of_code::solution!(9);
+advent_of_code::solution!(2023, 9);
 
 fn parse_input(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
     let single_sequence = terminated(separated_list1(space1, i64), opt(line_ending));
@@ -56,13 +56,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 }
@@ -7,7 +7,7 @@ use nom::{
 };
 use num::integer::div_rem;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2023, 10);
 
 fn _pretty_input(input: &str) -> String {
     input
@@ -183,18 +183,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(4));
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
+            "examples", PUZZLE, 3,
         ));
         assert_eq!(result, Some(4));
     }
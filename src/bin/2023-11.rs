@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use pathfinding::matrix::Matrix;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2023, 11);
 
 type GalaxiesMap = Matrix<char>;
 fn parse_input(input: &str) -> GalaxiesMap {
@@ -63,13 +63,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(374));
     }
 
     #[test]
     fn test_part_two() {
-        let _result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let _result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         // assert_eq!(result, Some(8410));
     }
 }
@@ -11,7 +11,7 @@ use nom::{
     Finish, IResult,
 };
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(2023, 12);
 
 #[derive(Debug, Clone)]
 struct SpringConditions {
@@ -123,22 +123,22 @@ fn count_solutions(springs: &[SpringConditions], repeat: usize) -> u64 {
     count
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str) -> Result<u64, Error<String>> {
     let spring_conditions: Vec<SpringConditions> = input
         .lines()
-        .map(|line| SpringConditions::from_str(line).unwrap())
-        .collect();
+        .map(SpringConditions::from_str)
+        .collect::<Result<_, _>>()?;
 
-    Some(count_solutions(&spring_conditions, 1))
+    Ok(count_solutions(&spring_conditions, 1))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> Result<u64, Error<String>> {
     let spring_conditions: Vec<SpringConditions> = input
         .lines()
-        .map(|line| SpringConditions::from_str(line).unwrap())
-        .collect();
+        .map(SpringConditions::from_str)
+        .collect::<Result<_, _>>()?;
 
-    Some(count_solutions(&spring_conditions, 5))
+    Ok(count_solutions(&spring_conditions, 5))
 }
 
 #[cfg(test)]
@@ -147,13 +147,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(525152));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(525152));
     }
 }
@@ -1,6 +1,6 @@
 use std::cmp;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2023, 13);
 
 #[derive(Debug, Default)]
 struct Pattern {
@@ -116,13 +116,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(405));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(400));
     }
 }
@@ -7,7 +7,7 @@ use indexmap::IndexMap;
 
 use pathfinding::matrix::{directions, Matrix};
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2023, 14);
 
 type Direction = (isize, isize);
 type Position = (usize, usize);
@@ -92,13 +92,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(136));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(64));
     }
 }
@@ -8,7 +8,7 @@ use nom::{
 };
 use num::Complex;
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(2023, 18);
 
 type Direction = Complex<isize>;
 const UP: Direction = Complex::<isize>::new(0, -1);
@@ -107,13 +107,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(62));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(952408144115));
     }
 }
@@ -14,7 +14,7 @@ use nom::{
 };
 use num::integer::lcm;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(2023, 20);
 
 const PUSH_TIMES: usize = 1000;
 
@@ -200,12 +200,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result_0 = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 0,
+            "examples", PUZZLE, 0,
         ));
         assert_eq!(result_0, Some(32000000));
 
         let result_1 = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result_1, Some(11687500));
     }
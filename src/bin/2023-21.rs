@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 
 use pathfinding::{directed::bfs::bfs_reach, grid::Grid, matrix::directions::DIRECTIONS_4};
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(2023, 21);
 
 type Map = Grid;
 type Coord = (usize, usize);
@@ -225,14 +225,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let _result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let _result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         // assert_eq!(result, Some(16));
     }
 
     #[test]
     fn test_part_two() {
         let _result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         // assert_eq!(result, Some(324));
     }
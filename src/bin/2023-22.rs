@@ -12,7 +12,7 @@ use nom::{
 };
 use pathfinding::directed::dfs::dfs_reach;
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(2023, 22);
 
 #[derive(Debug)]
 struct Coord3d {
@@ -276,28 +276,28 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(5));
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(3));
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
+            "examples", PUZZLE, 3,
         ));
         assert_eq!(result, Some(2));
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
+            "examples", PUZZLE, 4,
         ));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(7));
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 5,
+            "examples", PUZZLE, 5,
         ));
         assert_eq!(result, Some(2));
     }
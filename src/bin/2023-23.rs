@@ -8,7 +8,7 @@ use pathfinding::matrix::{
     Matrix,
 };
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(2023, 23);
 
 type Coord = (usize, usize);
 type ValidMovesFn = fn(Coord, &Matrix<char>) -> Vec<Coord>;
@@ -275,13 +275,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(94));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(154));
     }
 }
@@ -8,7 +8,7 @@ use nom::{
 };
 use num::Zero;
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(2023, 24);
 
 type Coord3D = (i64, i64, i64);
 type CoordFloat3D = (f64, f64, f64);
@@ -148,13 +148,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one_test(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one_test(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(47));
     }
 }
@@ -12,11 +12,11 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(2023, 25);
 
 type Component<'a> = (&'a str, Vec<&'a str>);
 
-fn component_connections(input: &str) -> IResult<&str, Component> {
+fn component_connections(input: &str) -> IResult<&str, Component<'_>> {
     let (i, component) = separated_pair(
         alpha1,
         char(':'),
@@ -25,7 +25,7 @@ fn component_connections(input: &str) -> IResult<&str, Component> {
     Ok((i, component))
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Component>> {
+fn parse_input(input: &str) -> IResult<&str, Vec<Component<'_>>> {
     many1(component_connections)(input)
 }
 
@@ -60,7 +60,9 @@ pub fn part_one(input: &str) -> Option<usize> {
 
     const SAMPLE_SIZE: usize = 100;
 
-    let component_names: Vec<_> = components_graph.keys().cloned().collect();
+    // NOTE: the sample has to be taken from, and ties broken in, a fixed order. `HashMap` iterates
+    // in a random order, which made the result differ between runs despite the seeded `rng`.
+    let component_names: Vec<_> = components_graph.keys().cloned().sorted().collect();
     let mut rng = StdRng::seed_from_u64(0);
 
     let mut components_sample = Vec::new();
@@ -83,7 +85,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     let edges_frequencies = visited_edges_frequency.iter().collect_vec();
     let top_three = edges_frequencies
         .iter()
-        .sorted_by_key(|(_, count)| count)
+        .sorted_by_key(|(edge, count)| (*count, *edge))
         .rev()
         .take(3)
         .collect_vec();
@@ -115,7 +117,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(54));
     }
 }
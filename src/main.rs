@@ -1,77 +1,242 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+#[cfg(feature = "all")]
+use advent_of_code::template::commands::all;
+use advent_of_code::template::commands::{
+    download, examples, progress, read, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
+/// Registry of all scaffolded solutions, generated by `build.rs`.
+#[cfg(feature = "all")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::solve;
+    use advent_of_code::template::config::{config, set_overrides};
+    use advent_of_code::template::{Day, PuzzleId, Threshold, Year};
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
-            day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            puzzle: PuzzleId,
+            options: solve::Options,
+            watch: bool,
         },
+        #[cfg(feature = "all")]
         All {
-            release: bool,
+            year: Year,
+            timeout: Option<Duration>,
+            jobs: usize,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            baseline: Option<String>,
+            check: Option<Threshold>,
+            timeout: Option<Duration>,
+        },
+        TimeCompare {
+            against: Option<String>,
+        },
+        TimeHistory,
+        Progress {
+            year: Year,
+            store: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Reads the `--year` flag, falling back to the year configured in `AOC_YEAR` or `aoc.toml`.
+    fn year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        args.opt_value_from_str("--year")?
+            .or_else(Year::configured)
+            .ok_or_else(|| "no year specified, pass `--year` or set `year` in `aoc.toml`.".into())
+    }
+
+    /// Reads the `--year` flag and the day, which is expected as the next free argument.
+    fn puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = year(args)?;
+        let day: Day = args.free_from_str()?;
+        Ok(PuzzleId::new(year, day))
+    }
+
+    /// Reads the `--timeout` flag in seconds, falling back to the `timeout` of `aoc.toml`. `0` disables the timeout.
+    fn timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        Ok(match args.opt_value_from_str::<_, u64>("--timeout")? {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => config().timeout,
+        })
+    }
+
+    /// Reads the `--jobs` flag, the number of days to run at the same time. Defaults to `1`.
+    fn jobs(args: &mut pico_args::Arguments) -> Result<usize, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--jobs")? {
+            Some(0) => Err("`--jobs` must be at least 1.".into()),
+            jobs => Ok(jobs.unwrap_or(1)),
+        }
+    }
+
+    /// Reads the `--example[=k]` flag. `k` has to be attached with `=`, so that `--example 1` reads
+    /// `1` as the day instead of as the example.
+    fn example(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Option<u8>>, Box<dyn std::error::Error>> {
+        if args.contains("--example") {
+            return Ok(Some(None));
+        }
+        Ok(args.opt_value_from_str("--example")?.map(Some))
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let subcommand = args.subcommand()?;
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        // NOTE: applied before reading any other flag, as their defaults depend on the configuration.
+        let overrides: Vec<String> = args.values_from_str("--config")?;
+        set_overrides(&overrides)?;
+
+        let app_args = match subcommand.as_deref() {
+            #[cfg(feature = "all")]
+            Some("all") => {
+                // NOTE: the alias always builds an optimized binary, `--release` is accepted for
+                // compatibility and has no further effect.
+                args.contains("--release");
+                AppArguments::All {
+                    year: year(&mut args)?,
+                    timeout: timeout(&mut args)?,
+                    jobs: jobs(&mut args)?,
+                }
+            }
+            Some("time") if args.contains("--compare") => AppArguments::TimeCompare {
+                against: args.opt_value_from_str("--against")?,
             },
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory,
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let check = args.contains("--check");
+                let threshold: Option<Threshold> = args.opt_value_from_str("--threshold")?;
+
+                if jobs(&mut args)? > 1 {
+                    return Err("`cargo time` does not run days in parallel, as that would skew the timings.".into());
+                }
 
                 AppArguments::Time {
+                    year: year(&mut args)?,
                     all,
+                    baseline: args.opt_value_from_str("--baseline")?,
+                    check: (check || threshold.is_some()).then(|| threshold.unwrap_or_default()),
+                    timeout: timeout(&mut args)?,
                     day: args.opt_free_from_str()?,
                     store,
                 }
             }
+            Some("progress") => AppArguments::Progress {
+                store: args.contains("--store"),
+                year: year(&mut args)?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
                 download: args.contains("--download"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
+                puzzle: puzzle(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let part: Option<u8> = args.opt_value_from_str("--part")?;
+                if part.is_some_and(|part| !(1..=2).contains(&part)) {
+                    return Err("`--part` must be 1 or 2.".into());
+                }
+
+                let submit_part: Option<u8> = args.opt_value_from_str("--submit")?;
+                if submit_part.is_some_and(|part| !(1..=2).contains(&part)) {
+                    return Err("`--submit` must be 1 or 2.".into());
+                }
+                if part.is_some() && submit_part.is_some() && part != submit_part {
+                    return Err("`--submit` must submit the part selected with `--part`.".into());
+                }
+
+                let input: Option<String> = args.opt_value_from_str("--input")?;
+                let example = example(&mut args)?;
+                if example.is_some() && input.is_some() {
+                    return Err("`--example` and `--input` can not be combined.".into());
+                }
+
+                let watch = args.contains("--watch");
+                if watch && input.as_deref() == Some("-") {
+                    return Err("`--watch` can not read the input from stdin.".into());
+                }
+
+                let puzzle = puzzle(&mut args)?;
+                let options = solve::Options {
+                    release: args.contains("--release"),
+                    submit_part,
+                    dhat: args.contains("--dhat"),
+                    profile_cpu: args.contains("--profile-cpu"),
+                    memory: args.contains("--memory"),
+                    record: args.contains("--record"),
+                    all_inputs: args.contains("--all-inputs"),
+                    test: args.contains("--test"),
+                    part,
+                    input,
+                    example,
+                };
+
+                // NOTE: runs write answers and submissions, which would trigger or repeat every run.
+                if watch && (options.submit_part.is_some() || options.record) {
+                    return Err(
+                        "`--watch` can not be combined with `--submit` or `--record`.".into(),
+                    );
+                }
+
+                if options.profile_cpu && (options.dhat || options.test || options.all_inputs) {
+                    return Err(
+                        "`--profile-cpu` can not be combined with `--dhat`, `--test` or `--all-inputs`.".into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    puzzle,
+                    options,
+                    watch,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -100,29 +265,53 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            #[cfg(feature = "all")]
+            AppArguments::All {
+                year,
+                timeout,
+                jobs,
+            } => all::handle(&solutions::registry(), year, timeout, jobs),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                baseline,
+                check,
+                timeout,
+            } => time::handle(year, day, all, store, baseline, check, timeout),
+            AppArguments::TimeCompare { against } => time::compare(against.as_deref()),
+            AppArguments::TimeHistory => time::history(),
+            AppArguments::Progress { year, store } => progress::handle(year, store),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle } => examples::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
+                download,
+                template,
+                answer_type,
+            } => {
+                scaffold::handle(puzzle, template.as_deref(), answer_type.as_deref());
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                puzzle,
+                options,
+                watch: true,
+            } => solve::watch(puzzle, &options),
+            AppArguments::Solve {
+                puzzle, options, ..
+            } => solve::handle(puzzle, &options),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, None, None);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
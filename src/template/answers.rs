@@ -0,0 +1,242 @@
+/// Stores the accepted answers of each day, so that refactored solutions can be checked for regressions.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::runner::PartOutput;
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// The accepted answers of both parts of a puzzle.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Result of comparing an answer against the stored one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerCheck {
+    Match,
+    Mismatch { expected: String },
+    Unknown,
+}
+
+#[must_use]
+pub fn get_path_for_answers(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path("answers", "json")
+}
+
+impl Answers {
+    /// Rehydrate answers for a puzzle from its JSON file. If not present, returns empty answers.
+    pub fn read(puzzle: PuzzleId) -> Self {
+        Answers::read_path(&get_path_for_answers(puzzle))
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_path(path: &Path) -> Self {
+        let s = fs::read_to_string(path)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from);
+
+        match s {
+            Ok(answers) => answers,
+            Err(e) => {
+                if path.exists() {
+                    eprintln!("{}: {e}", path.display());
+                }
+                Answers::default()
+            }
+        }
+    }
+
+    /// Dehydrate answers for a puzzle to its JSON file.
+    pub fn store(&self, puzzle: PuzzleId) -> Result<(), io::Error> {
+        let path = get_path_for_answers(puzzle);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Compare an answer against the stored answer of `part`.
+    /// A missing answer counts as a mismatch if an answer has been stored before.
+    pub fn check(&self, part: u8, answer: Option<&str>) -> AnswerCheck {
+        match (self.get(part), answer) {
+            (None, _) => AnswerCheck::Unknown,
+            (Some(expected), Some(answer)) if expected == answer => AnswerCheck::Match,
+            (Some(expected), _) => AnswerCheck::Mismatch {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+/// Store the answer for a part of a puzzle, keeping the answer of the other part.
+pub fn record(puzzle: PuzzleId, part: u8, answer: &str) {
+    let mut answers = Answers::read(puzzle);
+    answers.set(part, answer);
+
+    match answers.store(puzzle) {
+        Ok(()) => println!(
+            "Recorded answer for part {part} in \"{}\".",
+            get_path_for_answers(puzzle).display()
+        ),
+        Err(e) => eprintln!("Failed to record answer: {e}"),
+    }
+}
+
+/// Assert that an answer matches the stored answer. Does nothing if no answer has been stored.
+/// Panics if the part returned an error.
+/// Used by the tests generated by the `solution!` macro.
+pub fn assert_answer(puzzle: PuzzleId, part: u8, answer: impl PartOutput) {
+    let answer = match answer.to_answer() {
+        Ok(answer) => answer,
+        Err(e) => panic!("{puzzle}, part {part}: failed with error: {e}"),
+    };
+
+    match Answers::read(puzzle).check(part, answer.as_deref()) {
+        AnswerCheck::Match => {}
+        AnswerCheck::Unknown => eprintln!("No stored answer for {puzzle}, part {part}."),
+        AnswerCheck::Mismatch { expected } => panic!(
+            "{puzzle}, part {part}: expected stored answer {expected}, got {}",
+            answer.unwrap_or_else(|| "none".into())
+        ),
+    }
+}
+
+impl Display for AnswerCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerCheck::Match => write!(f, "✓"),
+            AnswerCheck::Mismatch { expected } => {
+                write!(f, "✗ expected {ANSI_BOLD}{expected}{ANSI_RESET}")
+            }
+            AnswerCheck::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "part_1".into(),
+            value.part_1.map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "part_2".into(),
+            value.part_2.map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_2 to be null or string.")?;
+
+        Ok(Answers {
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AnswerCheck, Answers};
+    use tinyjson::JsonValue;
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            part_1: Some("42".into()),
+            part_2: None,
+        }
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.check(1, Some("42")), AnswerCheck::Match);
+        assert_eq!(
+            answers.check(1, Some("41")),
+            AnswerCheck::Mismatch {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            answers.check(1, None),
+            AnswerCheck::Mismatch {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.check(2, Some("1")), AnswerCheck::Unknown);
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = get_mock_answers();
+        answers.set(2, "7");
+        assert_eq!(answers.get(1), Some("42"));
+        assert_eq!(answers.get(2), Some("7"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        Answers::try_from(r#"{ "part_1": 1 }"#.to_string()).unwrap();
+    }
+}
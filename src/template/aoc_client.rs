@@ -0,0 +1,437 @@
+/// Built-in client for the Advent of Code website.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::PuzzleId;
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    /// The website at `base_url` responded with an error status.
+    Http {
+        base_url: String,
+        status: u16,
+        body: String,
+    },
+    /// The website at `base_url` could not be reached.
+    Transport {
+        base_url: String,
+        message: String,
+    },
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create the file \"~/.adventofcode.session\"."
+            ),
+            AocClientError::Http {
+                base_url, status, ..
+            } => write!(f, "{base_url} responded with status {status}."),
+            AocClientError::Transport { base_url, message } => {
+                write!(f, "could not reach {base_url}: {message}")
+            }
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// Outcome of submitting an answer, as reported by the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently. Contains the remaining wait time, if reported.
+    RateLimited(Option<String>),
+    /// The part has already been solved or is not unlocked yet.
+    WrongLevel,
+    /// The response could not be interpreted. Contains the response message.
+    Unknown(String),
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmissionOutcome::Incorrect => write!(f, "That's not the right answer."),
+            SubmissionOutcome::TooHigh => {
+                write!(f, "That's not the right answer, your answer is too high.")
+            }
+            SubmissionOutcome::TooLow => {
+                write!(f, "That's not the right answer, your answer is too low.")
+            }
+            SubmissionOutcome::RateLimited(Some(wait)) => {
+                write!(f, "You gave an answer too recently, {wait} left to wait.")
+            }
+            SubmissionOutcome::RateLimited(None) => write!(f, "You gave an answer too recently."),
+            SubmissionOutcome::WrongLevel => {
+                write!(f, "This part is either already solved or not unlocked yet.")
+            }
+            SubmissionOutcome::Unknown(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+        }
+    }
+
+    /// Creates a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, the file at `AOC_SESSION_FILE` or `~/.adventofcode.session`.
+    ///  2. the base url is read from `AOC_BASE_URL`, defaulting to `https://adventofcode.com`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetch the input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle));
+        self.get(&url)
+    }
+
+    /// Fetch the description of a puzzle as HTML. Contains one `<article>` per unlocked part.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.puzzle_url(puzzle))?;
+        Ok(extract_articles(&html).join("\n\n"))
+    }
+
+    /// Submit an answer for a part of a puzzle.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| self.request_error(e))?
+            .into_string()?;
+        Ok(parse_submission_outcome(&html))
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| self.request_error(e))?
+            .into_string()?)
+    }
+
+    fn request_error(&self, e: ureq::Error) -> AocClientError {
+        let base_url = self.base_url.clone();
+        match e {
+            ureq::Error::Status(status, response) => AocClientError::Http {
+                base_url,
+                status,
+                body: response.into_string().unwrap_or_default(),
+            },
+            ureq::Error::Transport(e) => AocClientError::Transport {
+                base_url,
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let path = env::var("AOC_SESSION_FILE")
+        .map(PathBuf::from)
+        .ok()
+        .or_else(|| {
+            env::var("HOME")
+                .or_else(|_| env::var("USERPROFILE"))
+                .ok()
+                .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+        })?;
+
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extract the contents of all `<article>` elements of a page.
+fn extract_articles(html: &str) -> Vec<&str> {
+    html.split("<article")
+        .skip(1)
+        .filter_map(|s| {
+            let start = s.find('>')? + 1;
+            let end = s.find("</article>")?;
+            Some(&s[start..end])
+        })
+        .collect()
+}
+
+fn parse_submission_outcome(html: &str) -> SubmissionOutcome {
+    let message = extract_articles(html)
+        .first()
+        .map_or_else(|| html_to_text(html), |article| html_to_text(article));
+
+    if message.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if message.contains("answer is too high") {
+        SubmissionOutcome::TooHigh
+    } else if message.contains("answer is too low") {
+        SubmissionOutcome::TooLow
+    } else if message.contains("That's not the right answer") {
+        SubmissionOutcome::Incorrect
+    } else if message.contains("You gave an answer too recently") {
+        let wait = message
+            .split("You have ")
+            .nth(1)
+            .and_then(|s| s.split(" left to wait").next())
+            .map(ToString::to_string);
+        SubmissionOutcome::RateLimited(wait)
+    } else if message.contains("You don't seem to be solving the right level") {
+        SubmissionOutcome::WrongLevel
+    } else {
+        SubmissionOutcome::Unknown(message.trim().to_string())
+    }
+}
+
+/// Render the HTML of a puzzle page as plain text, for printing it to the terminal.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        let name = tag
+            .trim_start_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+
+        match (name, tag.starts_with('/')) {
+            ("h2", false) => text.push('\n'),
+            ("h2" | "p" | "pre" | "ul", true) => text.push_str("\n\n"),
+            ("li", false) => text.push_str("- "),
+            ("li", true) => text.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+
+    text.push_str(rest);
+
+    unescape_html(&text).trim().to_string()
+}
+
+/// Extract the title of a puzzle from its description, e.g. `Trebuchet?!` from `<h2>--- Day 1: Trebuchet?! ---</h2>`.
+pub fn extract_title(html: &str) -> Option<String> {
+    let start = html.find("<h2")?;
+    let start = html[start..].find('>')? + start + 1;
+    let end = html[start..].find("</h2>")? + start;
+    let heading = unescape_html(&html[start..end]);
+    let title = heading.trim().trim_matches('-').split_once(": ")?.1.trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Replace the HTML entities used on puzzle pages with the characters they represent.
+pub fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_title, html_to_text, AocClient, SubmissionOutcome};
+    use crate::puzzle;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Starts a stand-in server that answers a single request with `body`.
+    /// Returns the base url and a handle resolving to the raw request.
+    fn serve_once(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (url, handle) = serve_once("1\n2\n3\n");
+        let client = AocClient::new(&url, "abc");
+
+        assert_eq!(client.input(puzzle!(2023, 1)).unwrap(), "1\n2\n3\n");
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input "));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn downloads_puzzle() {
+        let (url, _) = serve_once(
+            "<main><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>Hi</p></article></main>",
+        );
+        let client = AocClient::new(&url, "abc");
+
+        assert_eq!(
+            client.puzzle(puzzle!(2023, 1)).unwrap(),
+            "<h2>--- Day 1 ---</h2><p>Hi</p>"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, handle) = serve_once(
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "abc");
+
+        assert_eq!(
+            client.submit(puzzle!(2023, 2), 1, "42").unwrap(),
+            SubmissionOutcome::TooHigh
+        );
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/2/answer "));
+        assert!(request.ends_with("level=1&answer=42"));
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        let (url, _) = serve_once(
+            "<article><p>You gave an answer too recently. You have 4m 1s left to wait.</p></article>",
+        );
+        let client = AocClient::new(&url, "abc");
+
+        assert_eq!(
+            client.submit(puzzle!(2023, 2), 1, "42").unwrap(),
+            SubmissionOutcome::RateLimited(Some("4m 1s".into()))
+        );
+    }
+
+    #[test]
+    fn reports_the_base_url() {
+        // NOTE: bind and drop a listener to get a port that refuses connections.
+        let url = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let client = AocClient::new(&url, "abc");
+
+        let error = client.input(puzzle!(2023, 1)).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with(&format!("could not reach {url}: ")));
+    }
+
+    #[test]
+    fn renders_html_as_text() {
+        let text = html_to_text(
+            "<h2>--- Day 1 ---</h2><p>Sum <em>all</em> &lt;numbers&gt;:</p><pre><code>1\n2\n</code></pre><ul><li>a</li></ul>",
+        );
+        assert_eq!(text, "--- Day 1 ---\n\nSum all <numbers>:\n\n1\n2\n\n\n- a");
+    }
+
+    #[test]
+    fn extracts_titles() {
+        assert_eq!(
+            extract_title("<h2>--- Day 1: Trebuchet?! ---</h2><p>Hi</p>"),
+            Some("Trebuchet?!".into())
+        );
+        assert_eq!(
+            extract_title("<h2>--- Day 7: Camel &amp; Cards ---</h2>"),
+            Some("Camel & Cards".into())
+        );
+        assert_eq!(extract_title("<p>No title</p>"), None);
+    }
+}
@@ -0,0 +1,218 @@
+/// Compares fresh benchmarks against the stored timings, to catch performance regressions.
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::history::{compare, Change};
+use crate::template::timings::Timings;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// How much slower a step may get before it counts as a regression.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Threshold {
+    /// Relative change in percent, e.g. `10%`.
+    Relative(f64),
+    /// Absolute change, e.g. `50µs`.
+    Absolute(Duration),
+}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Threshold::Relative(10.0)
+    }
+}
+
+#[derive(Debug)]
+pub struct ThresholdFromStrError;
+
+impl std::error::Error for ThresholdFromStrError {}
+
+impl Display for ThresholdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting a percentage like `10%` or a duration like `500ns`, `50µs`, `2ms`, `1s`.",
+        )
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = ThresholdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(percent) = s.strip_suffix('%') {
+            return percent
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|p| *p >= 0.0)
+                .map(Threshold::Relative)
+                .ok_or(ThresholdFromStrError);
+        }
+
+        let unit_start = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or(ThresholdFromStrError)?;
+        let (value, unit) = s.split_at(unit_start);
+        let value: f64 = value.parse().map_err(|_| ThresholdFromStrError)?;
+
+        let seconds = match unit.trim() {
+            "ns" => value / 1e9,
+            "µs" | "us" => value / 1e6,
+            "ms" => value / 1e3,
+            "s" => value,
+            _ => return Err(ThresholdFromStrError),
+        };
+
+        Duration::try_from_secs_f64(seconds)
+            .map(Threshold::Absolute)
+            .map_err(|_| ThresholdFromStrError)
+    }
+}
+
+impl Display for Threshold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Threshold::Relative(percent) => write!(f, "{percent}%"),
+            Threshold::Absolute(duration) => write!(f, "{duration:?}"),
+        }
+    }
+}
+
+/// Outcome of checking a single step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// Within the threshold.
+    Ok,
+    /// Faster by more than the threshold.
+    Faster,
+    /// Slower by more than the threshold.
+    Slower,
+    /// No stored timing to compare against.
+    New,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Ok => "ok",
+            Verdict::Faster => "faster",
+            Verdict::Slower => "SLOWER",
+            Verdict::New => "new",
+        })
+    }
+}
+
+impl Threshold {
+    pub fn verdict(&self, change: &Change) -> Verdict {
+        let (Some(before), Some(after)) = (change.before, change.after) else {
+            return Verdict::New;
+        };
+
+        let exceeds = |from: Duration, to: Duration| match self {
+            Threshold::Relative(percent) => {
+                to.as_secs_f64() > from.as_secs_f64() * (1.0 + percent / 100.0)
+            }
+            Threshold::Absolute(delta) => to > from + *delta,
+        };
+
+        if exceeds(before, after) {
+            Verdict::Slower
+        } else if exceeds(after, before) {
+            Verdict::Faster
+        } else {
+            Verdict::Ok
+        }
+    }
+}
+
+/// Check all steps of `current` against `stored`, print a verdict table and return whether all steps passed.
+pub fn check(current: &Timings, stored: &Timings, threshold: &Threshold) -> bool {
+    let format = |d: Option<Duration>| d.map_or_else(|| "-".into(), |d| format!("{d:.1?}"));
+    let mut passed = true;
+
+    println!("{ANSI_BOLD}Checking against stored timings (threshold: {threshold}){ANSI_RESET}");
+    println!();
+    println!(
+        "| {:<7} | {:<6} | {:>10} | {:>10} | {:>8} | {:<7} |",
+        "Day", "Step", "Stored", "Current", "Change", "Verdict"
+    );
+    println!(
+        "|{:-<9}|{:-<8}|{:->12}|{:->12}|{:->10}|{:-<9}|",
+        "", "", "", "", "", ""
+    );
+
+    for (timing, changes) in compare(current, stored) {
+        for change in changes.iter().filter(|c| c.after.is_some()) {
+            let verdict = threshold.verdict(change);
+            passed &= verdict != Verdict::Slower;
+
+            println!(
+                "| {:<7} | {:<6} | {:>10} | {:>10} | {:>8} | {:<7} |",
+                timing.puzzle.to_string(),
+                change.step,
+                format(change.before),
+                format(change.after),
+                change
+                    .percent()
+                    .map_or_else(|| "-".into(), |p| format!("{p:+.1}%")),
+                verdict.to_string()
+            );
+        }
+    }
+
+    passed
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Threshold, Verdict};
+    use crate::template::history::Change;
+
+    fn change(before: Option<u64>, after: u64) -> Change {
+        Change {
+            step: "Part 1".into(),
+            before: before.map(Duration::from_micros),
+            after: Some(Duration::from_micros(after)),
+        }
+    }
+
+    #[test]
+    fn parses_thresholds() {
+        assert_eq!(
+            "10%".parse::<Threshold>().unwrap(),
+            Threshold::Relative(10.0)
+        );
+        assert_eq!(
+            "50µs".parse::<Threshold>().unwrap(),
+            Threshold::Absolute(Duration::from_micros(50))
+        );
+        assert_eq!(
+            "1.5ms".parse::<Threshold>().unwrap(),
+            Threshold::Absolute(Duration::from_micros(1500))
+        );
+        assert!("10".parse::<Threshold>().is_err());
+        assert!("-5%".parse::<Threshold>().is_err());
+        assert!("5h".parse::<Threshold>().is_err());
+    }
+
+    #[test]
+    fn judges_relative_changes() {
+        let threshold = Threshold::Relative(10.0);
+        assert_eq!(threshold.verdict(&change(Some(100), 105)), Verdict::Ok);
+        assert_eq!(threshold.verdict(&change(Some(100), 120)), Verdict::Slower);
+        assert_eq!(threshold.verdict(&change(Some(100), 80)), Verdict::Faster);
+        assert_eq!(threshold.verdict(&change(None, 80)), Verdict::New);
+    }
+
+    #[test]
+    fn judges_absolute_changes() {
+        let threshold = Threshold::Absolute(Duration::from_micros(50));
+        assert_eq!(threshold.verdict(&change(Some(100), 140)), Verdict::Ok);
+        assert_eq!(threshold.verdict(&change(Some(100), 160)), Verdict::Slower);
+        assert_eq!(threshold.verdict(&change(Some(100), 40)), Verdict::Faster);
+    }
+}
@@ -1,5 +1,25 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_puzzles,
+    registry::Registry,
+    run_multi::{run_multi, Runner},
+    Year,
+};
+
+pub fn handle(registry: &Registry, year: Year, timeout: Option<Duration>, jobs: usize) {
+    // NOTE: the output of solutions can only be captured per day from their own processes.
+    let runner = if jobs > 1 {
+        Runner::ChildProcess {
+            is_release: !cfg!(debug_assertions),
+        }
+    } else {
+        Runner::InProcess(registry)
+    };
+
+    let summary = run_multi(&all_puzzles(year).collect(), &runner, false, timeout, jobs);
+
+    if !summary.is_success() {
+        process::exit(1);
+    }
 }
@@ -1,14 +1,47 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::integrity::{self, Checksums};
+use crate::template::{aoc_client::AocClient, PuzzleId};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(puzzle: PuzzleId) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up the aoc client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let result = client.input(puzzle).and_then(|input| {
+        // NOTE: do not overwrite a working input with an error message of the website.
+        if let Err(e) = integrity::validate(&input) {
+            eprintln!("failed to download {puzzle}: the downloaded input {e}.");
+            process::exit(1);
+        }
+
+        fs::create_dir_all(puzzle.data_dir("inputs"))?;
+        fs::write(&input_path, &input)?;
+        Checksums::record(puzzle, &input)?;
+        let description = client.puzzle(puzzle)?;
+        fs::create_dir_all(puzzle.data_dir("puzzles"))?;
+        fs::write(&puzzle_path, description)?;
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        eprintln!("failed to download {puzzle}: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
 }
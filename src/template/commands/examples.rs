@@ -0,0 +1,100 @@
+use std::{fs, path::Path, process};
+
+use crate::template::examples::{extract_examples, fill_test, use_part_example};
+use crate::template::PuzzleId;
+
+/// Write an example file, unless it already has contents.
+fn write_example(path: &Path, input: &str) {
+    if fs::read_to_string(path).is_ok_and(|s| !s.trim().is_empty()) {
+        println!(
+            "Skipped example file \"{}\", it is not empty.",
+            path.display()
+        );
+        return;
+    }
+
+    match fs::write(path, input) {
+        Ok(()) => println!("Wrote example to \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+pub fn handle(puzzle: PuzzleId) {
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+    let module_path = puzzle.bin_path();
+
+    let Ok(description) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read \"{}\", run `cargo download {} --year {}` first.",
+            puzzle_path.display(),
+            puzzle.day,
+            puzzle.year
+        );
+        process::exit(1);
+    };
+
+    let Ok(mut source) = fs::read_to_string(&module_path) else {
+        eprintln!(
+            "Could not read \"{}\", run `cargo scaffold {} --year {}` first.",
+            module_path.display(),
+            puzzle.day,
+            puzzle.year
+        );
+        process::exit(1);
+    };
+
+    if let Err(e) = fs::create_dir_all(puzzle.data_dir("examples")) {
+        eprintln!("Failed to create examples directory: {e}");
+        process::exit(1);
+    }
+
+    let examples = extract_examples(&description);
+    let first_input = examples.first().and_then(|e| e.input.as_ref());
+
+    for (example, (part, test_name)) in examples
+        .iter()
+        .zip([(1, "test_part_one"), (2, "test_part_two")])
+    {
+        // part two usually reuses the example of part one, a different one gets its own file.
+        match &example.input {
+            Some(input) if part == 1 => write_example(&puzzle.data_path("examples", "txt"), input),
+            Some(input) if Some(input) != first_input => {
+                let path = puzzle
+                    .data_dir("examples")
+                    .join(format!("{}-{part}.txt", puzzle.day));
+                write_example(&path, input);
+                if let Some(updated) = use_part_example(&source, test_name, part) {
+                    source = updated;
+                }
+            }
+            Some(_) => {}
+            None if part == 1 => println!("No example found for part {part}."),
+            None => {}
+        }
+
+        match &example.answer {
+            Some(answer) => match fill_test(&source, test_name, answer) {
+                Some(updated) => {
+                    println!("Expecting {answer} for the example of part {part}.");
+                    source = updated;
+                }
+                None => println!("Skipped `{test_name}`, it does not expect `None`."),
+            },
+            None => println!("No expected answer found for part {part}."),
+        }
+    }
+
+    if let Err(e) = fs::write(&module_path, source) {
+        eprintln!("Failed to update module file: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!(
+        "🎄 Updated the tests in \"{}\", double-check the extracted examples and answers.",
+        module_path.display()
+    );
+}
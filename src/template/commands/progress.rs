@@ -0,0 +1,20 @@
+use std::process;
+
+use crate::template::progress::Progress;
+use crate::template::{readme_stars, Year};
+
+pub fn handle(year: Year, store: bool) {
+    let progress = Progress::read(year);
+    progress.print_calendar();
+
+    if store {
+        println!();
+        match readme_stars::update(&progress) {
+            Ok(()) => println!("Stored updated stars."),
+            Err(e) => {
+                eprintln!("Failed to store updated stars: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
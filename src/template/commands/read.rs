@@ -1,15 +1,24 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    aoc_client::{html_to_text, AocClient},
+    PuzzleId,
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+pub fn handle(puzzle: PuzzleId) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up the aoc client: {e}");
+            process::exit(1);
+        }
     };
+
+    match client.puzzle(puzzle) {
+        Ok(puzzle) => println!("{}", html_to_text(&puzzle)),
+        Err(e) => {
+            eprintln!("failed to read {puzzle}: {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -1,19 +1,107 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::template::Day;
+use crate::template::aoc_client::extract_title;
+use crate::template::config::config;
+use crate::template::PuzzleId;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Directory of user-defined templates. A template in here takes precedence over a built-in one of the same name.
+const TEMPLATES_DIR: &str = "templates";
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "nom",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/nom.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+];
+
+/// Names of all available templates, user-defined ones first.
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+
+    for (name, _) in BUILTIN_TEMPLATES {
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+
+    names
+}
+
+fn load_template(name: &str) -> Result<String, std::io::Error> {
+    let custom_path = PathBuf::from(TEMPLATES_DIR).join(format!("{name}.txt"));
+    if custom_path.exists() {
+        return fs::read_to_string(custom_path);
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| template.to_string())
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "unknown template"))
+}
+
+/// Replace the placeholders of a template.
+fn render_template(template: &str, puzzle: PuzzleId, answer_type: &str) -> String {
+    // the title is only known if the puzzle has been downloaded before.
+    let title = fs::read_to_string(puzzle.data_path("puzzles", "md"))
+        .ok()
+        .and_then(|description| extract_title(&description))
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+
+    template
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%TITLE%", &title)
+        .replace("%ANSWER_TYPE%", answer_type)
+}
+
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -21,10 +109,19 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, template: Option<&str>, answer_type: Option<&str>) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.bin_path();
+
+    let template_name = template.unwrap_or(&config().scaffold.template);
+    let Ok(template) = load_template(template_name) else {
+        eprintln!(
+            "Unknown template `{template_name}`, available templates: {}.",
+            available_templates().join(", ")
+        );
+        process::exit(1);
+    };
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -35,12 +132,15 @@ pub fn handle(day: Day) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
+        render_template(
+            &template,
+            puzzle,
+            answer_type.unwrap_or(&config().scaffold.answer_type),
+        )
+        .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -50,7 +150,7 @@ pub fn handle(day: Day) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -60,7 +160,7 @@ pub fn handle(day: Day) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -69,5 +169,8 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
 }
@@ -1,34 +1,134 @@
-use std::process::{Command, Stdio};
+use std::io::{stdout, Write};
+use std::process::{self, ExitStatus, Stdio};
 
-use crate::template::Day;
+use crate::template::watch::Snapshot;
+use crate::template::{cargo_command, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Flags of the `solve` command.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub dhat: bool,
+    /// Sample the CPU usage of each part and write a flamegraph to `profiles/`.
+    pub profile_cpu: bool,
+    pub memory: bool,
+    pub submit_part: Option<u8>,
+    pub record: bool,
+    pub all_inputs: bool,
+    /// Run the tests of the solution instead of the solution itself.
+    pub test: bool,
+    /// Only run this part of the solution.
+    pub part: Option<u8>,
+    /// Read the input from this path instead of `data/{year}/inputs`, `-` reads stdin.
+    pub input: Option<String>,
+    /// Run an example instead of the input, `Some(None)` reads `NN.txt` and `Some(Some(k))` reads `NN-k.txt`.
+    pub example: Option<Option<u8>>,
+}
+
+fn command_args(puzzle: PuzzleId, options: &Options) -> Vec<String> {
+    let command = if options.test { "test" } else { "run" };
+    let mut cmd_args = vec![command.to_string(), "--bin".to_string(), puzzle.to_string()];
 
-    if dhat {
+    if options.dhat {
         cmd_args.extend([
             "--profile".to_string(),
             "dhat".to_string(),
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else if options.profile_cpu {
+        // NOTE: reuses the optimized profile with debug info of dhat, which symbolized stacks need.
+        cmd_args.extend([
+            "--profile".to_string(),
+            "dhat".to_string(),
+            "--features".to_string(),
+            "cpu-profile".to_string(),
+        ]);
+    } else {
+        if options.release {
+            cmd_args.push("--release".to_string());
+        }
+        if options.memory || cfg!(feature = "memory-stats") {
+            cmd_args.extend(["--features".to_string(), "memory-stats".to_string()]);
+        }
+    }
+
+    // the remaining flags are passed to the solution, tests do not know about them.
+    if options.test {
+        return cmd_args;
     }
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = options.submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    if options.record {
+        cmd_args.push("--record".to_string());
+    }
+
+    if options.all_inputs {
+        cmd_args.push("--all-inputs".to_string());
+    }
+
+    if options.profile_cpu {
+        cmd_args.push("--profile-cpu".to_string());
+    }
+
+    if let Some(part) = options.part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if let Some(input) = &options.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.clone());
+    }
+
+    if let Some(example) = options.example {
+        cmd_args.push(match example {
+            Some(k) => format!("--example={k}"),
+            None => "--example".to_string(),
+        });
+    }
+
+    cmd_args
+}
+
+fn run(puzzle: PuzzleId, options: &Options) -> ExitStatus {
+    let mut cmd = cargo_command()
+        .args(command_args(puzzle, options))
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap()
+}
+
+pub fn handle(puzzle: PuzzleId, options: &Options) {
+    let status = run(puzzle, options);
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Rerun the solution, or its tests, whenever one of its files changes. Runs until interrupted.
+pub fn watch(puzzle: PuzzleId, options: &Options) {
+    loop {
+        // NOTE: take the snapshot before running, so that changes made during the run trigger another one.
+        let snapshot = Snapshot::new(puzzle);
+
+        // clear the screen and move the cursor to the top left.
+        print!("\x1b[2J\x1b[H");
+        let _ = stdout().flush();
+
+        run(puzzle, options);
+
+        println!();
+        println!("{ANSI_ITALIC}Watching for changes, press Ctrl-C to stop.{ANSI_RESET}");
+        snapshot.wait_for_change(puzzle);
+    }
 }
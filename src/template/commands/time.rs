@@ -1,40 +1,133 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
+use crate::template::check::check;
+use crate::template::history::{print_comparison, History, Run};
+use crate::template::run_multi::{run_multi, Runner};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_puzzles, readme_benchmarks, Day, PuzzleId, Threshold, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    baseline: Option<String>,
+    threshold: Option<Threshold>,
+    timeout: Option<Duration>,
+) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let puzzles_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_puzzles(year).collect()
+            } else if threshold.is_some() {
+                // when checking, rerun all puzzles that have stored timings.
+                all_puzzles(year)
+                    .filter(|puzzle| stored_timings.data.iter().any(|t| t.puzzle == *puzzle))
+                    .collect()
             } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                // when the `--all` flag is not set, filter out puzzles that are fully benched.
+                all_puzzles(year)
+                    .filter(|puzzle| !stored_timings.is_day_complete(*puzzle))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |day| HashSet::from([PuzzleId::new(year, day)]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let is_release = true;
+    let runner = Runner::ChildProcess { is_release };
+    let summary = run_multi(&puzzles_to_run, &runner, true, timeout, 1);
+    let timings = summary.timings.clone().unwrap_or_default();
+
+    if let Some(threshold) = threshold {
+        println!();
+        let passed = check(&timings, &stored_timings, &threshold);
+        if !passed {
+            eprintln!();
+            eprintln!("Performance regressed beyond the threshold of {threshold}.");
+        }
+        if !passed || !summary.is_success() {
+            process::exit(1);
+        }
+        return;
+    }
 
     if store {
+        let mut history = History::read_from_file();
+        history.push(Run::new(timings.clone(), is_release, baseline));
+        if let Err(e) = history.store_file() {
+            eprintln!("Failed to store benchmark history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(&merged_timings, &history.previous_timings()) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    if !summary.is_success() {
+        process::exit(1);
+    }
+}
+
+/// Print the stored benchmark runs.
+pub fn history() {
+    let history = History::read_from_file();
+
+    if history.runs.is_empty() {
+        println!("No benchmark runs stored yet, run `cargo time --store` first.");
+        return;
+    }
+
+    for (i, run) in history.runs.iter().enumerate() {
+        let puzzles: Vec<String> = run
+            .timings
+            .data
+            .iter()
+            .map(|t| t.puzzle.to_string())
+            .collect();
+        println!("#{}: {} [{}]", i + 1, run.describe(), puzzles.join(", "));
+    }
+}
+
+/// Compare the latest stored run against an earlier one.
+/// `against` refers to a run as described in [`History::find`], defaults to the previous run.
+pub fn compare(against: Option<&str>) {
+    let history = History::read_from_file();
+
+    let Some(latest) = history.runs.last().map(|run| (history.runs.len(), run)) else {
+        eprintln!("No benchmark runs stored yet, run `cargo time --store` first.");
+        process::exit(1);
+    };
+
+    let reference = match against {
+        Some(reference) => history.find(reference),
+        None => history
+            .runs
+            .len()
+            .checked_sub(1)
+            .and_then(|n| history.find(&n.to_string())),
+    };
+
+    match reference {
+        Some(reference) => print_comparison(latest, reference),
+        None => {
+            eprintln!(
+                "Could not find a run to compare against, see `cargo time --history` for stored runs."
+            );
+            process::exit(1);
+        }
+    }
 }
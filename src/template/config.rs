@@ -0,0 +1,410 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// The file and each of its keys are optional, missing keys keep their defaults. Every key can be
+/// overridden on the command line with `--config <key>=<value>`, e.g. `--config bench.budget_ms=2000`.
+/// Overrides are passed on to the solution binaries in the `AOC_CONFIG` environment variable, as
+/// one `key = value` per line.
+use std::{env, fs, io, path::PathBuf, process, sync::OnceLock, time::Duration};
+
+use toml::{Table, Value};
+
+use crate::template::{Year, DEFAULT_TIMEOUT};
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+static OVERRIDES_ENV: &str = "AOC_CONFIG";
+static YEAR_ENV: &str = "AOC_YEAR";
+
+const DATA_FOLDERS: [&str; 5] = ["inputs", "examples", "puzzles", "answers", "submissions"];
+
+/// Location of the data files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataConfig {
+    /// Root directory of the data files, which also holds the timings.
+    pub dir: PathBuf,
+    /// Folder of each kind of data file, relative to `dir`. `{year}` is replaced with the year.
+    pub folders: Vec<(String, String)>,
+}
+
+/// Defaults of `cargo scaffold`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaffoldConfig {
+    pub template: String,
+    pub answer_type: String,
+}
+
+/// Settings of `--time` benchmarks. Each part runs for about `budget`, within the sample limits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub budget: Duration,
+    pub min_samples: u32,
+    pub max_samples: u32,
+}
+
+/// Files updated with the tables of the readme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadmeConfig {
+    /// File with the stars table, see `cargo progress --store`.
+    pub stars: PathBuf,
+    /// Files with benchmark tables, see `cargo time --store`.
+    pub benchmarks: Vec<PathBuf>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Default year of all commands. `AOC_YEAR` takes precedence over `aoc.toml`, overrides over both.
+    pub year: Option<Year>,
+    /// Default timeout of each day in `cargo all` and `cargo time`, `None` disables it.
+    pub timeout: Option<Duration>,
+    pub data: DataConfig,
+    pub scaffold: ScaffoldConfig,
+    pub bench: BenchConfig,
+    pub readme: ReadmeConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            timeout: Some(DEFAULT_TIMEOUT),
+            data: DataConfig {
+                dir: PathBuf::from("data"),
+                folders: DATA_FOLDERS
+                    .iter()
+                    .map(|folder| (folder.to_string(), format!("{{year}}/{folder}")))
+                    .collect(),
+            },
+            scaffold: ScaffoldConfig {
+                template: "default".into(),
+                answer_type: "u32".into(),
+            },
+            bench: BenchConfig {
+                budget: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10000,
+            },
+            readme: ReadmeConfig {
+                stars: PathBuf::from("README.md"),
+                benchmarks: vec![PathBuf::from("README.md")],
+            },
+        }
+    }
+}
+
+/// The configuration of the project, loaded on first use. Exits if it is invalid.
+pub fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid configuration: {e}");
+            process::exit(1);
+        }
+    })
+}
+
+/// Add `key=value` overrides for this process and the processes it spawns.
+/// Has to be called before the configuration is used.
+pub fn set_overrides(overrides: &[String]) -> Result<(), String> {
+    if overrides.is_empty() {
+        return Ok(());
+    }
+
+    let mut config = Config::default();
+    for s in overrides {
+        config.apply_override(s)?;
+    }
+
+    let mut lines: Vec<String> = env::var(OVERRIDES_ENV).into_iter().collect();
+    lines.extend(overrides.iter().cloned());
+    env::set_var(OVERRIDES_ENV, lines.join("\n"));
+    Ok(())
+}
+
+impl Config {
+    /// Read `aoc.toml`, then apply `AOC_YEAR` and the overrides in `AOC_CONFIG`.
+    pub fn load() -> Result<Self, String> {
+        let mut config = Config::default();
+
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => config
+                .apply_document(&s)
+                .map_err(|e| format!("{CONFIG_FILE_PATH}: {e}"))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("{CONFIG_FILE_PATH}: {e}")),
+        }
+
+        if let Ok(year) = env::var(YEAR_ENV) {
+            let year = year
+                .parse()
+                .map_err(|_| format!("{YEAR_ENV}: `{year}` is not a valid year."))?;
+            config.year = Some(year);
+        }
+
+        if let Ok(overrides) = env::var(OVERRIDES_ENV) {
+            for line in overrides.lines().filter(|l| !l.trim().is_empty()) {
+                config
+                    .apply_override(line)
+                    .map_err(|e| format!("{OVERRIDES_ENV}: {e}"))?;
+            }
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Directory of a kind of data file of a year, e.g. `data/2023/inputs`.
+    pub fn data_dir(&self, year: Year, folder: &str) -> PathBuf {
+        let path = self
+            .data
+            .folders
+            .iter()
+            .find(|(name, _)| name == folder)
+            .map_or_else(|| format!("{year}/{folder}"), |(_, path)| path.clone());
+
+        self.data
+            .dir
+            .join(path.replace("{year}", &year.to_string()))
+    }
+
+    /// Path of a file in the root of the data directory, e.g. `data/timings.json`.
+    pub fn data_file(&self, name: &str) -> PathBuf {
+        self.data.dir.join(name)
+    }
+
+    fn apply_document(&mut self, s: &str) -> Result<(), String> {
+        let table: Table = s.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        self.apply(String::new(), Value::Table(table))
+    }
+
+    /// Apply a `key=value` override. Values that are not valid TOML are read as strings.
+    fn apply_override(&mut self, s: &str) -> Result<(), String> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected `key=value`, got `{s}`."))?;
+        let value = parse_value(value).unwrap_or_else(|_| Value::String(value.trim().into()));
+        self.apply(key.trim().to_string(), value)
+    }
+
+    /// Set `key` to `value`. The keys of a table are set one by one, prefixed with `key`.
+    fn apply(&mut self, key: String, value: Value) -> Result<(), String> {
+        match value {
+            Value::Table(table) => table.into_iter().try_for_each(|(name, value)| {
+                let key = if key.is_empty() {
+                    name
+                } else {
+                    format!("{key}.{name}")
+                };
+                self.apply(key, value)
+            }),
+            value => self.set(&key, value),
+        }
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), String> {
+        match key {
+            "year" => {
+                let year = integer(&value, key)?;
+                self.year = Some(
+                    u16::try_from(year)
+                        .ok()
+                        .and_then(Year::new)
+                        .ok_or(format!("`{year}` is not a valid year."))?,
+                );
+            }
+            "timeout" => {
+                let seconds = unsigned(&value, key)?;
+                self.timeout = (seconds > 0).then(|| Duration::from_secs(seconds));
+            }
+            "data.dir" => self.data.dir = string(value, key)?.into(),
+            "scaffold.template" => self.scaffold.template = string(value, key)?,
+            "scaffold.answer_type" => self.scaffold.answer_type = string(value, key)?,
+            "bench.budget_ms" => self.bench.budget = Duration::from_millis(unsigned(&value, key)?),
+            "bench.min_samples" => self.bench.min_samples = samples(&value, key)?,
+            "bench.max_samples" => self.bench.max_samples = samples(&value, key)?,
+            "readme.stars" => self.readme.stars = string(value, key)?.into(),
+            "readme.benchmarks" => {
+                self.readme.benchmarks = match value {
+                    Value::Array(values) => values
+                        .into_iter()
+                        .map(|v| string(v, key).map(PathBuf::from))
+                        .collect::<Result<_, _>>()?,
+                    value => vec![string(value, key)?.into()],
+                };
+            }
+            _ => {
+                let folder = key
+                    .strip_prefix("data.")
+                    .filter(|folder| DATA_FOLDERS.contains(folder))
+                    .ok_or(format!("unknown key `{key}`."))?;
+                let path = string(value, key)?;
+                match self
+                    .data
+                    .folders
+                    .iter_mut()
+                    .find(|(name, _)| name == folder)
+                {
+                    Some((_, existing)) => *existing = path,
+                    None => self.data.folders.push((folder.into(), path)),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.bench.min_samples > self.bench.max_samples {
+            return Err("`bench.min_samples` must not exceed `bench.max_samples`.".into());
+        }
+        Ok(())
+    }
+}
+
+fn string(value: Value, key: &str) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(format!("expected `{key}` to be a string.")),
+    }
+}
+
+fn integer(value: &Value, key: &str) -> Result<i64, String> {
+    value
+        .as_integer()
+        .ok_or_else(|| format!("expected `{key}` to be an integer."))
+}
+
+fn unsigned(value: &Value, key: &str) -> Result<u64, String> {
+    u64::try_from(integer(value, key)?).map_err(|_| format!("expected `{key}` to be positive."))
+}
+
+fn samples(value: &Value, key: &str) -> Result<u32, String> {
+    match u32::try_from(unsigned(value, key)?) {
+        Ok(samples) if samples > 0 => Ok(samples),
+        _ => Err(format!(
+            "expected `{key}` to be between 1 and {}.",
+            u32::MAX
+        )),
+    }
+}
+
+/// Parse a single TOML value, e.g. `500` or `["README.md", "2022/README.md"]`.
+fn parse_value(s: &str) -> Result<Value, String> {
+    let mut table: Table = format!("value = {s}")
+        .parse()
+        .map_err(|e: toml::de::Error| e.message().to_string())?;
+
+    match (table.remove("value"), table.is_empty()) {
+        (Some(value), true) => Ok(value),
+        _ => Err(format!("expected a single value, got `{s}`.")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_value, Config, Value};
+    use crate::year;
+    use std::{path::PathBuf, time::Duration};
+
+    #[test]
+    fn parses_values() {
+        assert_eq!(parse_value(" 1_000 "), Ok(Value::Integer(1000)));
+        assert_eq!(
+            parse_value(r#""a \"b\"""#),
+            Ok(Value::String("a \"b\"".into()))
+        );
+        assert_eq!(
+            parse_value("'C:\\data'"),
+            Ok(Value::String("C:\\data".into()))
+        );
+        assert_eq!(
+            parse_value("[\n  \"a\",\n  'b', # comment\n]"),
+            Ok(Value::Array(vec![
+                Value::String("a".into()),
+                Value::String("b".into())
+            ]))
+        );
+        assert_eq!(parse_value("true"), Ok(Value::Boolean(true)));
+        assert!(parse_value("\"open").is_err());
+        assert!(parse_value("data").is_err());
+        assert!(parse_value("1 2").is_err());
+        assert!(parse_value("1\nyear = 2022").is_err());
+    }
+
+    #[test]
+    fn reads_document() {
+        let mut config = Config::default();
+        config
+            .apply_document(
+                r#"
+                # project settings
+                year = 2022
+                timeout = 0
+                scaffold = { template = "grid", answer_type = "u64" }
+
+                [data]
+                dir = "other # not a comment"
+                inputs = "private/{year}"
+
+                [bench]
+                budget_ms = 500
+
+                [readme]
+                benchmarks = [
+                    "README.md",
+                    "2022/README.md",
+                ]
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(config.year, Some(year!(2022)));
+        assert_eq!(config.timeout, None);
+        assert_eq!(config.bench.budget, Duration::from_millis(500));
+        assert_eq!(config.bench.max_samples, 10000);
+        assert_eq!(config.scaffold.answer_type, "u64");
+        assert_eq!(
+            config.data_dir(year!(2022), "inputs"),
+            PathBuf::from("other # not a comment/private/2022")
+        );
+        assert_eq!(
+            config.data_dir(year!(2022), "examples"),
+            PathBuf::from("other # not a comment/2022/examples")
+        );
+        assert_eq!(config.readme.benchmarks.len(), 2);
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let mut config = Config::default();
+        config.apply_override("data.dir=fixtures").unwrap();
+        config
+            .apply_override("scaffold.template = \"grid\"")
+            .unwrap();
+        config.apply_override("bench.max_samples=100").unwrap();
+        config
+            .apply_override("readme={ stars = \"STARS.md\" }")
+            .unwrap();
+
+        assert_eq!(config.data.dir, PathBuf::from("fixtures"));
+        assert_eq!(config.scaffold.template, "grid");
+        assert_eq!(config.bench.max_samples, 100);
+        assert_eq!(config.readme.stars, PathBuf::from("STARS.md"));
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        let mut config = Config::default();
+        assert!(config.apply_override("nope=1").is_err());
+        assert!(config.apply_override("year=1999").is_err());
+        assert!(config.apply_override("timeout=-1").is_err());
+        assert!(config.apply_override("bench.min_samples=0").is_err());
+        assert!(config.apply_override("timeout=1.5").is_err());
+
+        config.apply_override("bench.min_samples=20000").unwrap();
+        assert!(config.validate().is_err());
+        assert!(config.apply_document("[bench]\nbudget_ms").is_err());
+    }
+}
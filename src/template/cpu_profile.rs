@@ -0,0 +1,134 @@
+/// Samples the CPU usage of a solution part and writes a flamegraph of it, see `cargo solve <day> --profile-cpu`.
+///
+/// Uses the in-process sampling profiler of `pprof`, which requires Linux and the `cpu-profile`
+/// feature. The part is run repeatedly for the bench budget of `aoc.toml`, so that fast parts
+/// still collect enough samples.
+use std::path::PathBuf;
+
+use crate::template::PuzzleId;
+
+/// Directory the profiles are written to.
+static PROFILES_DIR: &str = "profiles";
+
+/// Paths of the flamegraph and the folded stacks of a part, e.g. `profiles/2023-01-part-1.svg`.
+#[cfg_attr(
+    not(all(feature = "cpu-profile", target_os = "linux")),
+    allow(dead_code)
+)]
+pub fn profile_paths(puzzle: PuzzleId, part: u8) -> (PathBuf, PathBuf) {
+    let path = PathBuf::from(PROFILES_DIR).join(format!("{puzzle}-part-{part}"));
+    (path.with_extension("svg"), path.with_extension("folded"))
+}
+
+/// A stack in the folded format of `inferno`, root first: `main;solve;part_one 42`.
+#[cfg_attr(
+    not(all(feature = "cpu-profile", target_os = "linux")),
+    allow(dead_code)
+)]
+fn fold(thread: &str, symbols: &[String], count: isize) -> String {
+    let mut line = thread.to_string();
+    for symbol in symbols {
+        line.push(';');
+        line.push_str(symbol);
+    }
+    format!("{line} {count}")
+}
+
+/// Profile `func` and write its flamegraph and folded stacks. Returns the paths of both files.
+#[cfg(all(feature = "cpu-profile", target_os = "linux"))]
+pub fn profile<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Result<(PathBuf, PathBuf), String> {
+    use std::{fs, hint::black_box, time::Instant};
+
+    use pprof::flamegraph;
+
+    use crate::template::config::config;
+
+    // NOTE: an odd frequency avoids sampling in lockstep with periodic work of the solution.
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(997)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let budget = config().bench.budget;
+    let start = Instant::now();
+    loop {
+        black_box(func(black_box(input.clone())));
+        if start.elapsed() >= budget {
+            break;
+        }
+    }
+
+    let report = guard.report().build().map_err(|e| e.to_string())?;
+    drop(guard);
+
+    let mut lines: Vec<String> = report
+        .data
+        .iter()
+        .map(|(frames, count)| {
+            let symbols: Vec<String> = frames
+                .frames
+                .iter()
+                .rev()
+                .flat_map(|frame| frame.iter().rev().map(ToString::to_string))
+                .collect();
+            fold(&frames.thread_name_or_id(), &symbols, *count)
+        })
+        .collect();
+    lines.sort();
+
+    if lines.is_empty() {
+        return Err("no samples were collected.".into());
+    }
+
+    let (svg_path, folded_path) = profile_paths(puzzle, part);
+    fs::create_dir_all(PROFILES_DIR).map_err(|e| e.to_string())?;
+    fs::write(&folded_path, lines.join("\n") + "\n").map_err(|e| e.to_string())?;
+
+    let mut options = flamegraph::Options::default();
+    options.title = format!("{puzzle}, part {part}");
+    let file = fs::File::create(&svg_path).map_err(|e| e.to_string())?;
+    flamegraph::from_lines(&mut options, lines.iter().map(String::as_str), file)
+        .map_err(|e| e.to_string())?;
+
+    Ok((svg_path, folded_path))
+}
+
+/// Profile `func` and write its flamegraph and folded stacks. Not available in this build.
+#[cfg(not(all(feature = "cpu-profile", target_os = "linux")))]
+pub fn profile<I: Clone, T>(
+    _func: impl Fn(I) -> T,
+    _input: I,
+    _puzzle: PuzzleId,
+    _part: u8,
+) -> Result<(PathBuf, PathBuf), String> {
+    Err("CPU profiling requires Linux and the `cpu-profile` feature, use `cargo solve <day> --profile-cpu`.".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fold, profile_paths};
+    use crate::puzzle;
+    use std::path::PathBuf;
+
+    #[test]
+    fn folds_stacks() {
+        let symbols = vec!["main".to_string(), "2023_01::part_one".to_string()];
+        assert_eq!(fold("main", &symbols, 42), "main;main;2023_01::part_one 42");
+        assert_eq!(fold("worker", &[], 1), "worker 1");
+    }
+
+    #[test]
+    fn builds_profile_paths() {
+        let (svg, folded) = profile_paths(puzzle!(2023, 1), 2);
+        assert_eq!(svg, PathBuf::from("profiles/2023-01-part-2.svg"));
+        assert_eq!(folded, PathBuf::from("profiles/2023-01-part-2.folded"));
+    }
+}
@@ -0,0 +1,202 @@
+/// Extracts examples and their expected answers from a downloaded puzzle description.
+///
+/// Puzzle descriptions are stored as the HTML of their `<article>` elements, one per unlocked part.
+/// The example of a part is its first `<pre><code>` block, the expected answer is the last
+/// emphasized code (`<code><em>42</em></code>`) of the part, which is how the puzzles state the
+/// result for the example.
+use crate::template::answers::Answers;
+use crate::template::aoc_client::unescape_html;
+
+/// Example input and expected answer of a single part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+/// Extract the examples of all parts of a puzzle description, starting with part one.
+pub fn extract_examples(html: &str) -> Vec<Example> {
+    html.split("<h2")
+        .skip(1)
+        .map(|part| Example {
+            input: extract_first_block(part),
+            answer: extract_last_answer(part),
+        })
+        .collect()
+}
+
+/// Expected answers of the parts whose example is `input`. Part two reuses the example of
+/// part one if its description does not contain one.
+pub fn example_answers(html: &str, input: &str) -> Answers {
+    let examples = extract_examples(html);
+    let first_input = examples.first().and_then(|e| e.input.as_deref());
+    let mut answers = Answers::default();
+
+    for (example, part) in examples.iter().zip(1..=2) {
+        let example_input = example.input.as_deref().or(first_input);
+        if let (Some(example_input), Some(answer)) = (example_input, &example.answer) {
+            if example_input.trim() == input.trim() {
+                answers.set(part, answer);
+            }
+        }
+    }
+
+    answers
+}
+
+/// Contents of the first `<pre><code>` block of a part.
+fn extract_first_block(html: &str) -> Option<String> {
+    let start = html.find("<pre><code>")? + "<pre><code>".len();
+    let end = html[start..].find("</code></pre>")? + start;
+    Some(unescape_html(&strip_tags(&html[start..end])))
+}
+
+/// The last emphasized code of a part, either written as `<code><em>..</em></code>` or `<em><code>..</code></em>`.
+fn extract_last_answer(html: &str) -> Option<String> {
+    ["<code><em>", "<em><code>"]
+        .iter()
+        .filter_map(|open| {
+            let start = html.rfind(open)? + open.len();
+            let end = html[start..].find('<')? + start;
+            Some((start, unescape_html(&html[start..end])))
+        })
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, answer)| answer)
+        .filter(|answer| !answer.trim().is_empty())
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Fill in the expected answer of a test generated from the scaffold templates.
+/// Only replaces asserts that still expect `None`, so tests edited by hand are kept.
+/// Returns `None` if the test function or its assert could not be found.
+pub fn fill_test(source: &str, test_name: &str, answer: &str) -> Option<String> {
+    let test_start = source.find(&format!("fn {test_name}()"))?;
+    let test_end = source[test_start + 1..]
+        .find("fn ")
+        .map_or(source.len(), |i| i + test_start + 1);
+
+    let placeholder = "assert_eq!(result, None);";
+    let assert_start = source[test_start..test_end].find(placeholder)? + test_start;
+
+    let expected = if answer.parse::<i64>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}")
+    };
+
+    Some(format!(
+        "{}assert_eq!(result, Some({expected}));{}",
+        &source[..assert_start],
+        &source[assert_start + placeholder.len()..]
+    ))
+}
+
+/// Make a test generated from the scaffold templates read the example of a specific part, e.g. `01-2.txt`.
+/// Returns `None` if the test function could not be found.
+pub fn use_part_example(source: &str, test_name: &str, part: u8) -> Option<String> {
+    let test_start = source.find(&format!("fn {test_name}()"))?;
+    let call = "read_file(\"examples\", PUZZLE)";
+    let call_start = source[test_start..].find(call)? + test_start;
+
+    Some(format!(
+        "{}read_file_part(\"examples\", PUZZLE, {part}){}",
+        &source[..call_start],
+        &source[call_start + call.len()..]
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{example_answers, extract_examples, fill_test, use_part_example, Example};
+    use crate::template::answers::Answers;
+
+    const MOCK_PUZZLE: &str = "<h2>--- Day 1: Test ---</h2><p>For example:</p>\
+        <pre><code>1abc2\n<em>pqr</em>3stu8vwx\n</code></pre>\
+        <p>Here, the values are <code>12</code> and <code>38</code>. \
+        Adding these together produces <code><em>50</em></code>.</p>\
+        \n\n<h2 id=\"part2\">--- Part Two ---</h2><p>Again:</p>\
+        <pre><code>&lt;two1nine&gt;\n</code></pre><p>The result is <em><code>29</code></em>.</p>";
+
+    const MOCK_MODULE: &str = "    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file(\"examples\", PUZZLE));
+        assert_eq!(result, None);
+    }
+";
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(
+            extract_examples(MOCK_PUZZLE),
+            vec![
+                Example {
+                    input: Some("1abc2\npqr3stu8vwx\n".into()),
+                    answer: Some("50".into()),
+                },
+                Example {
+                    input: Some("<two1nine>\n".into()),
+                    answer: Some("29".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_example_answers() {
+        let answers = example_answers(MOCK_PUZZLE, "1abc2\npqr3stu8vwx");
+        assert_eq!(answers.get(1), Some("50"));
+        assert_eq!(answers.get(2), None);
+
+        let answers = example_answers(MOCK_PUZZLE, "<two1nine>\n");
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("29"));
+
+        let reused = MOCK_PUZZLE.replace("<pre><code>&lt;two1nine&gt;\n</code></pre>", "");
+        let answers = example_answers(&reused, "1abc2\npqr3stu8vwx\n");
+        assert_eq!(answers.get(2), Some("29"));
+
+        assert_eq!(example_answers(MOCK_PUZZLE, "other"), Answers::default());
+    }
+
+    #[test]
+    fn handles_locked_parts() {
+        let examples = extract_examples("<h2>--- Day 1 ---</h2><p>Nothing to see.</p>");
+        assert_eq!(examples, vec![Example::default()]);
+    }
+
+    #[test]
+    fn fills_tests() {
+        let source = fill_test(MOCK_MODULE, "test_part_two", "29").unwrap();
+        let source = use_part_example(&source, "test_part_two", 2).unwrap();
+        let source = fill_test(&source, "test_part_one", "abc").unwrap();
+
+        assert!(source.contains("assert_eq!(result, Some(\"abc\"));"));
+        assert!(source.contains(
+            "read_file_part(\"examples\", PUZZLE, 2));\n        assert_eq!(result, Some(29));"
+        ));
+        assert!(fill_test(&source, "test_part_one", "1").is_none());
+    }
+}
@@ -0,0 +1,500 @@
+/// Keeps every stored benchmark run, so that runs can be compared against each other.
+/// `data/timings.json` only holds the latest timing of each puzzle, this module appends.
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::Error,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::config::config;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+/// A single stored benchmark run and the environment it was recorded in.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub commit: Option<String>,
+    pub rustc: Option<String>,
+    pub profile: String,
+    /// Name given with `--baseline`, if any.
+    pub baseline: Option<String>,
+    pub timings: Timings,
+}
+
+/// All stored benchmark runs, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl Run {
+    /// Create a run from fresh timings, collecting metadata about the current environment.
+    pub fn new(timings: Timings, is_release: bool, baseline: Option<String>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Run {
+            timestamp,
+            commit: get_commit(),
+            rustc: get_rustc_version(),
+            profile: if is_release { "release" } else { "debug" }.into(),
+            baseline,
+            timings,
+        }
+    }
+
+    /// One-line summary of the run, e.g. `2023-12-01 08:00 UTC, abc1234, release`.
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format_timestamp(self.timestamp)];
+        parts.extend(self.commit.clone());
+        parts.push(self.profile.clone());
+        parts.extend(self.rustc.clone());
+        if let Some(baseline) = &self.baseline {
+            parts.push(format!("baseline `{baseline}`"));
+        }
+        parts.join(", ")
+    }
+}
+
+impl History {
+    /// Dehydrate the history to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(config().data_file(HISTORY_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file() -> Self {
+        let path = config().data_file(HISTORY_FILE_NAME);
+        let s = fs::read_to_string(&path)
+            .map_err(|x| x.to_string())
+            .and_then(History::try_from);
+
+        match s {
+            Ok(history) => history,
+            Err(e) => {
+                if path.exists() {
+                    eprintln!("{e}");
+                }
+                History::default()
+            }
+        }
+    }
+
+    pub fn push(&mut self, run: Run) {
+        self.runs.push(run);
+    }
+
+    /// Find a run by reference, which is one of:
+    ///  1. a run number, as printed by `cargo time --history`.
+    ///  2. the name of a baseline. If several runs share the name, the latest one is used.
+    ///  3. a prefix of a commit hash. If several runs match, the latest one is used.
+    pub fn find(&self, reference: &str) -> Option<(usize, &Run)> {
+        if let Ok(number) = reference.parse::<usize>() {
+            return number
+                .checked_sub(1)
+                .and_then(|i| self.runs.get(i))
+                .map(|run| (number, run));
+        }
+
+        let numbered = || {
+            self.runs
+                .iter()
+                .enumerate()
+                .map(|(i, run)| (i + 1, run))
+                .rev()
+        };
+
+        numbered()
+            .find(|(_, run)| run.baseline.as_deref() == Some(reference))
+            .or_else(|| {
+                numbered().find(|(_, run)| {
+                    run.commit
+                        .as_ref()
+                        .is_some_and(|commit| commit.starts_with(reference))
+                })
+            })
+    }
+
+    /// Timings of the run before the latest run of each puzzle, used to show the change since the last run.
+    pub fn previous_timings(&self) -> Timings {
+        let mut seen: HashMap<PuzzleId, usize> = HashMap::new();
+        let mut data = vec![];
+
+        for timing in self.runs.iter().rev().flat_map(|run| &run.timings.data) {
+            let count = seen.entry(timing.puzzle).or_default();
+            *count += 1;
+            if *count == 2 {
+                data.push(timing.clone());
+            }
+        }
+
+        data.sort_unstable_by_key(|t| t.puzzle);
+        Timings { data }
+    }
+}
+
+/// Change of a single step between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub step: String,
+    pub before: Option<Duration>,
+    pub after: Option<Duration>,
+}
+
+impl Change {
+    /// Relative change in percent. Positive values mean the step got slower.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self) -> Option<f64> {
+        let (before, after) = (
+            self.before?.as_nanos() as f64,
+            self.after?.as_nanos() as f64,
+        );
+        (before > 0.0).then(|| (after - before) / before * 100.0)
+    }
+}
+
+/// Compare the steps of all puzzles of `latest` against the same steps in `reference`.
+/// Durations are the medians of the benchmarks.
+pub fn compare(latest: &Timings, reference: &Timings) -> Vec<(Timing, Vec<Change>)> {
+    latest
+        .data
+        .iter()
+        .map(|timing| {
+            let before = reference.data.iter().find(|t| t.puzzle == timing.puzzle);
+
+            let median = |t: Option<&Timing>, step: &str| {
+                let stats = match step {
+                    "Parse" => t?.parse_stats.as_ref(),
+                    "Part 1" => t?.part_1_stats.as_ref(),
+                    _ => t?.part_2_stats.as_ref(),
+                };
+                stats.map(|s| s.median)
+            };
+
+            let changes = ["Parse", "Part 1", "Part 2"]
+                .into_iter()
+                .map(|step| Change {
+                    step: step.into(),
+                    before: median(before, step),
+                    after: median(Some(timing), step),
+                })
+                .filter(|change| change.before.is_some() || change.after.is_some())
+                .collect();
+
+            (timing.clone(), changes)
+        })
+        .collect()
+}
+
+/// Print the comparison of two runs.
+pub fn print_comparison(latest: (usize, &Run), reference: (usize, &Run)) {
+    println!(
+        "{ANSI_BOLD}Run #{}{ANSI_RESET} ({})",
+        latest.0,
+        latest.1.describe()
+    );
+    println!(
+        "{ANSI_BOLD}compared to run #{}{ANSI_RESET} ({})",
+        reference.0,
+        reference.1.describe()
+    );
+
+    for (timing, changes) in compare(&latest.1.timings, &reference.1.timings) {
+        println!();
+        println!(
+            "{ANSI_BOLD}{} Day {}{ANSI_RESET}",
+            timing.puzzle.year, timing.puzzle.day
+        );
+
+        for change in changes {
+            let format = |d: Option<Duration>| d.map_or_else(|| "-".into(), |d| format!("{d:.1?}"));
+            let percent = change
+                .percent()
+                .map_or_else(String::new, |p| format!(" ({p:+.1}%)"));
+            println!(
+                "{}: {} → {}{percent}",
+                change.step,
+                format(change.before),
+                format(change.after)
+            );
+        }
+    }
+}
+
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+fn get_rustc_version() -> Option<String> {
+    // NOTE: cargo sets `RUSTC` for the processes it runs, which points at the active toolchain.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let output = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Format a unix timestamp as UTC date and time, e.g. `2023-12-01 08:00 UTC`.
+fn format_timestamp(timestamp: u64) -> String {
+    // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.runs.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(History {
+            runs: json_runs
+                .iter()
+                .map(Run::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let string_or_null =
+            |x: &Option<String>| x.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), string_or_null(&value.commit));
+        map.insert("rustc".into(), string_or_null(&value.rustc));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("baseline".into(), string_or_null(&value.baseline));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let string_or_null = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected run.{key} to be null or string."))
+        };
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected run.profile to be a string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Run {
+            timestamp,
+            commit: string_or_null("commit")?.cloned(),
+            rustc: string_or_null("rustc")?.cloned(),
+            profile: profile.clone(),
+            baseline: string_or_null("baseline")?.cloned(),
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, format_timestamp, History, Run};
+    use crate::puzzle;
+    use crate::template::{
+        runner::BenchStats,
+        timings::{Timing, Timings},
+    };
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn stats(median: u64) -> Option<BenchStats> {
+        Some(BenchStats::from_samples(&[Duration::from_nanos(median)]))
+    }
+
+    fn get_mock_run(commit: &str, baseline: Option<&str>, median: u64) -> Run {
+        Run {
+            timestamp: 1_701_417_600,
+            commit: Some(commit.into()),
+            rustc: Some("rustc 1.74.0".into()),
+            profile: "release".into(),
+            baseline: baseline.map(Into::into),
+            timings: Timings {
+                data: vec![Timing {
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
+                    part_1: Some(format!("{median}ns")),
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: stats(median),
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    total_nanos: median as f64,
+                }],
+            },
+        }
+    }
+
+    fn get_mock_history() -> History {
+        History {
+            runs: vec![
+                get_mock_run("aaa1111", Some("main"), 200),
+                get_mock_run("bbb2222", None, 100),
+                get_mock_run("ccc3333", None, 150),
+            ],
+        }
+    }
+
+    #[test]
+    fn finds_runs() {
+        let history = get_mock_history();
+        assert_eq!(history.find("2").map(|(i, _)| i), Some(2));
+        assert_eq!(history.find("main").map(|(i, _)| i), Some(1));
+        assert_eq!(history.find("ccc").map(|(i, _)| i), Some(3));
+        assert!(history.find("4").is_none());
+        assert!(history.find("0").is_none());
+        assert!(history.find("ddd").is_none());
+    }
+
+    #[test]
+    fn finds_previous_timings() {
+        let mut history = get_mock_history();
+        history.runs[2].timings.data[0].puzzle = puzzle!(2023, 2);
+
+        let previous = history.previous_timings();
+        assert_eq!(previous.data.len(), 1);
+        assert_eq!(previous.data[0].puzzle, puzzle!(2023, 1));
+        assert_eq!(previous.data[0].total_nanos, 200.0);
+    }
+
+    #[test]
+    fn compares_runs() {
+        let history = get_mock_history();
+        let comparison = compare(&history.runs[2].timings, &history.runs[0].timings);
+        assert_eq!(comparison.len(), 1);
+
+        let changes = &comparison[0].1;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].step, "Part 1");
+        assert_eq!(changes[0].percent(), Some(-25.0));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let history = get_mock_history();
+        let json = JsonValue::from(history).stringify().unwrap();
+        let parsed = History::try_from(json).unwrap();
+        assert_eq!(parsed.runs.len(), 3);
+        assert_eq!(parsed.runs[0].baseline.as_deref(), Some("main"));
+        assert_eq!(parsed.runs[1].baseline, None);
+        assert_eq!(parsed.runs[2].commit.as_deref(), Some("ccc3333"));
+        assert_eq!(parsed.runs[2].timings.data[0].part_1_stats, stats(150));
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(1_701_417_600), "2023-12-01 08:00 UTC");
+    }
+}
@@ -0,0 +1,164 @@
+/// Runs a solution against all known inputs of a puzzle, e.g. the inputs of other people.
+///
+/// Besides the main input `data/{year}/inputs/{day}.txt`, additional inputs are read from
+/// `data/{year}/inputs/{day}/*.txt`. Each of them can be paired with its known answers in a JSON file
+/// of the same name next to it (`alice.txt` → `alice.json`), in the format of `data/{year}/answers`.
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::answers::{get_path_for_answers, AnswerCheck, Answers};
+use crate::template::integrity;
+use crate::template::registry::Solution;
+use crate::template::runner::{print_parse_result, print_part_result};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// Directory holding the additional inputs of a puzzle, e.g. `data/2023/inputs/01`.
+#[must_use]
+pub fn get_path_for_inputs(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_dir("inputs").join(puzzle.day.to_string())
+}
+
+/// Paths of all inputs of a puzzle, starting with the main input. Missing files are skipped.
+pub fn input_paths(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let main_input = puzzle.data_path("inputs", "txt");
+
+    let mut additional: Vec<PathBuf> = fs::read_dir(get_path_for_inputs(puzzle))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect()
+        })
+        .unwrap_or_default();
+    additional.sort();
+
+    main_input
+        .exists()
+        .then_some(main_input)
+        .into_iter()
+        .chain(additional)
+        .collect()
+}
+
+/// Path of the known answers of an input. The main input uses the answers stored in `data/{year}/answers`.
+#[must_use]
+pub fn get_path_for_input_answers(puzzle: PuzzleId, input: &Path) -> PathBuf {
+    if input == puzzle.data_path("inputs", "txt") {
+        get_path_for_answers(puzzle)
+    } else {
+        input.with_extension("json")
+    }
+}
+
+/// Run a solution against all inputs of its puzzle and check the answers.
+/// Prints a summary and exits with a non-zero code if any input failed.
+pub fn run_all(solution: &Solution) {
+    let puzzle = solution.puzzle;
+    let inputs = input_paths(puzzle);
+
+    if inputs.is_empty() {
+        eprintln!(
+            "No inputs found for {puzzle}, expected \"{}\" or \"{}/*.txt\".",
+            puzzle.data_path("inputs", "txt").display(),
+            get_path_for_inputs(puzzle).display()
+        );
+        process::exit(1);
+    }
+
+    let mut failed: Vec<(&PathBuf, String)> = vec![];
+
+    for (i, path) in inputs.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}{}{ANSI_RESET}", path.display());
+
+        // NOTE: only the main input has a checksum of its download.
+        let input = if *path == puzzle.data_path("inputs", "txt") {
+            integrity::read_verified(puzzle)
+        } else {
+            integrity::read_validated(path)
+        };
+
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("The input {e}.");
+                failed.push((path, format!("input {e}")));
+                continue;
+            }
+        };
+
+        let answers = Answers::read_path(&get_path_for_input_answers(puzzle, path));
+
+        let Ok(result) = panic::catch_unwind(AssertUnwindSafe(|| (solution.solve)(&input, false)))
+        else {
+            failed.push((path, "panicked".into()));
+            continue;
+        };
+
+        if let Some(parse) = &result.parse {
+            print_parse_result(parse);
+        }
+
+        let mut reasons = vec![];
+        for part in &result.parts {
+            let check = answers.check(part.part, part.answer.as_deref());
+            print_part_result(part, &check);
+            if part.error.is_some() {
+                reasons.push(format!("part {} failed", part.part));
+            } else if matches!(check, AnswerCheck::Mismatch { .. }) {
+                reasons.push(format!("wrong answer for part {}", part.part));
+            }
+        }
+
+        if !reasons.is_empty() {
+            failed.push((path, reasons.join(", ")));
+        }
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}{} of {} inputs passed.{ANSI_RESET}",
+        inputs.len() - failed.len(),
+        inputs.len()
+    );
+
+    if !failed.is_empty() {
+        for (path, reason) in &failed {
+            eprintln!("Failed: {} ({reason})", path.display());
+        }
+        process::exit(1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{get_path_for_input_answers, get_path_for_inputs};
+    use crate::puzzle;
+
+    #[test]
+    fn builds_paths() {
+        let puzzle = puzzle!(2023, 1);
+        assert_eq!(
+            get_path_for_inputs(puzzle),
+            PathBuf::from("data/2023/inputs/01")
+        );
+        assert_eq!(
+            get_path_for_input_answers(puzzle, Path::new("data/2023/inputs/01.txt")),
+            PathBuf::from("data/2023/answers/01.json")
+        );
+        assert_eq!(
+            get_path_for_input_answers(puzzle, Path::new("data/2023/inputs/01/alice.txt")),
+            PathBuf::from("data/2023/inputs/01/alice.json")
+        );
+    }
+}
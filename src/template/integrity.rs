@@ -0,0 +1,214 @@
+/// Checks puzzle inputs before a solution runs on them.
+///
+/// `cargo scaffold` creates an empty input and a failed download might store an error page of the
+/// website, both of which would make a solution panic or print a nonsensical answer. A checksum of
+/// every downloaded input is stored in `data/checksums.json`, so that inputs changed since their
+/// download can be reported.
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path};
+use tinyjson::JsonValue;
+
+use crate::template::config::config;
+use crate::template::PuzzleId;
+
+static CHECKSUMS_FILE_NAME: &str = "checksums.json";
+
+/// Reason a puzzle input can not be used.
+#[derive(Debug)]
+pub enum InputError {
+    Missing,
+    Empty,
+    ErrorPage,
+    IO(io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing => f.write_str("is missing"),
+            InputError::Empty => f.write_str("is empty"),
+            InputError::ErrorPage => f.write_str("is an error page of the website"),
+            InputError::IO(e) => write!(f, "could not be read: {e}"),
+        }
+    }
+}
+
+/// Messages the website responds with instead of an input, e.g. for a missing session or a locked puzzle.
+const ERROR_MESSAGES: [&str; 3] = [
+    "Puzzle inputs differ by user.",
+    "Please don't repeatedly request this endpoint",
+    "404 Not Found",
+];
+
+/// Check that `input` looks like a puzzle input.
+pub fn validate(input: &str) -> Result<(), InputError> {
+    let trimmed = input.trim();
+    let start = trimmed.get(..16).unwrap_or(trimmed).to_ascii_lowercase();
+
+    let is_html = start.starts_with("<!doctype") || start.starts_with("<html");
+    let is_error_message = ERROR_MESSAGES.iter().any(|m| trimmed.starts_with(m));
+
+    if trimmed.is_empty() {
+        Err(InputError::Empty)
+    } else if is_html || is_error_message {
+        Err(InputError::ErrorPage)
+    } else {
+        Ok(())
+    }
+}
+
+/// Read and validate an input file, e.g. one of the additional inputs of `cargo solve --all-inputs`.
+pub fn read_validated(path: &Path) -> Result<String, InputError> {
+    let input = fs::read_to_string(path).map_err(|e| {
+        if e.kind() == io::ErrorKind::NotFound {
+            InputError::Missing
+        } else {
+            InputError::IO(e)
+        }
+    })?;
+
+    validate(&input)?;
+    Ok(input)
+}
+
+/// Read and validate the input of a puzzle. Warns if it changed since it was downloaded.
+pub fn read_verified(puzzle: PuzzleId) -> Result<String, InputError> {
+    let input = read_validated(&puzzle.data_path("inputs", "txt"))?;
+
+    if Checksums::read().is_changed(puzzle, &input) {
+        eprintln!(
+            "Warning: the input of {puzzle} changed since it was downloaded, run `cargo download {} --year {}` to restore it.",
+            puzzle.day.into_inner(),
+            puzzle.year
+        );
+    }
+
+    Ok(input)
+}
+
+/// 64-bit FNV-1a hash of the input, as hex. Stable across platforms and compiler versions.
+pub fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// Checksums of the downloaded inputs, by puzzle.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Checksums(HashMap<String, String>);
+
+impl Checksums {
+    /// Rehydrate checksums from their JSON file. If not present, returns empty checksums.
+    pub fn read() -> Self {
+        let path = config().data_file(CHECKSUMS_FILE_NAME);
+        let s = fs::read_to_string(&path)
+            .map_err(|x| x.to_string())
+            .and_then(Checksums::try_from);
+
+        match s {
+            Ok(checksums) => checksums,
+            Err(e) => {
+                if path.exists() {
+                    eprintln!("{}: {e}", path.display());
+                }
+                Checksums::default()
+            }
+        }
+    }
+
+    /// Dehydrate checksums to their JSON file.
+    pub fn store(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(config().data_file(CHECKSUMS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Store the checksum of a downloaded input.
+    pub fn record(puzzle: PuzzleId, input: &str) -> Result<(), io::Error> {
+        let mut checksums = Checksums::read();
+        checksums.0.insert(puzzle.to_string(), checksum(input));
+        checksums.store()
+    }
+
+    /// Whether `input` differs from the input that was downloaded. Inputs without a checksum are unchanged.
+    pub fn is_changed(&self, puzzle: PuzzleId, input: &str) -> bool {
+        self.0
+            .get(&puzzle.to_string())
+            .is_some_and(|stored| *stored != checksum(input))
+    }
+}
+
+impl From<Checksums> for JsonValue {
+    fn from(value: Checksums) -> Self {
+        JsonValue::Object(
+            value
+                .0
+                .into_iter()
+                .map(|(puzzle, checksum)| (puzzle, JsonValue::String(checksum)))
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<String> for Checksums {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = value.parse::<JsonValue>().map_err(|x| x.to_string())?;
+        let object = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        object
+            .iter()
+            .map(|(puzzle, checksum)| {
+                let checksum = checksum
+                    .get::<String>()
+                    .ok_or(format!("expected checksum of `{puzzle}` to be a string."))?;
+                Ok((puzzle.clone(), checksum.clone()))
+            })
+            .collect::<Result<_, String>>()
+            .map(Checksums)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{checksum, validate, Checksums, InputError};
+    use crate::puzzle;
+
+    #[test]
+    fn validates_inputs() {
+        assert!(validate("1abc2\npqr3stu8vwx\n").is_ok());
+        assert!(matches!(validate(" \n"), Err(InputError::Empty)));
+        assert!(matches!(
+            validate("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InputError::ErrorPage)
+        ));
+        assert!(matches!(
+            validate("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Err(InputError::ErrorPage)
+        ));
+        // inputs may contain `<`, as long as they are not an html page.
+        assert!(validate("<<>>\n<>").is_ok());
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn detects_changed_inputs() {
+        let json = format!(r#"{{"2023-01":"{}"}}"#, checksum("input"));
+        let checksums = Checksums::try_from(json).unwrap();
+
+        assert!(!checksums.is_changed(puzzle!(2023, 1), "input"));
+        assert!(checksums.is_changed(puzzle!(2023, 1), "changed"));
+        assert!(!checksums.is_changed(puzzle!(2023, 2), "input"));
+    }
+}
@@ -0,0 +1,255 @@
+/// Lightweight heap statistics, collected by a counting global allocator.
+///
+/// With the `memory-stats` feature, the library installs [`CountingAlloc`] as the global allocator of
+/// every binary that links it. The allocator forwards to the system allocator and keeps per-thread
+/// counters, which [`measure`] reads before and after running a solution part.
+/// Allocations made on other threads, e.g. by `rayon`, are not attributed to the part.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use tinyjson::JsonValue;
+
+/// Heap usage of a single run of a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Highest number of bytes allocated at the same time, above what was allocated before the run.
+    pub peak_bytes: u64,
+    /// Sum of the sizes of all allocations.
+    pub total_bytes: u64,
+    pub allocations: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, total {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+/// Whether the counting allocator is installed. DHAT replaces it, as there can only be one global allocator.
+pub const IS_ENABLED: bool = cfg!(all(feature = "memory-stats", not(feature = "dhat-heap")));
+
+/// Run `func` and collect the heap usage of the current thread while it runs.
+/// Returns `None` as stats if the counting allocator is not installed.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if !IS_ENABLED {
+        return (func(), None);
+    }
+
+    let before = COUNTERS.with(|counters| {
+        let mut before = counters.get();
+        before.peak = before.current;
+        counters.set(before);
+        before
+    });
+
+    let result = func();
+    let after = COUNTERS.with(Cell::get);
+
+    let stats = MemoryStats {
+        peak_bytes: u64::try_from(after.peak - before.current).unwrap_or(0),
+        total_bytes: after.total - before.total,
+        allocations: after.allocations - before.allocations,
+    };
+
+    (result, Some(stats))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy)]
+struct Counters {
+    /// Bytes currently allocated by this thread. Can become negative if memory allocated by another thread is freed.
+    current: i64,
+    peak: i64,
+    total: u64,
+    allocations: u64,
+}
+
+thread_local! {
+    // NOTE: const-initialized and without a destructor, so that accessing it never allocates.
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { current: 0, peak: 0, total: 0, allocations: 0 })
+    };
+}
+
+/// Global allocator that counts the allocations of each thread and forwards them to [`System`].
+pub struct CountingAlloc;
+
+#[allow(clippy::cast_possible_wrap)]
+impl CountingAlloc {
+    fn record(allocated: usize, freed: usize) {
+        // NOTE: counters are unavailable while a thread shuts down, its last allocations are not counted.
+        let _ = COUNTERS.try_with(|counters| {
+            let mut c = counters.get();
+            c.current += allocated as i64 - freed as i64;
+            c.peak = c.peak.max(c.current);
+            if allocated > 0 {
+                c.total += allocated as u64;
+                c.allocations += 1;
+            }
+            counters.set(c);
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+#[cfg(all(feature = "memory-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected memory stats.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")?,
+            total_bytes: number("total_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure, MemoryStats, IS_ENABLED};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn measures_allocations() {
+        let (len, stats) = measure(|| {
+            let a = vec![0u8; 4096];
+            let b = vec![0u8; 1024];
+            a.len() + b.len()
+        });
+
+        assert_eq!(len, 5120);
+        match stats {
+            Some(stats) => {
+                assert_eq!(stats.allocations, 2);
+                assert_eq!(stats.total_bytes, 5120);
+                assert_eq!(stats.peak_bytes, 5120);
+            }
+            None => assert!(!IS_ENABLED),
+        }
+    }
+
+    #[test]
+    fn roundtrips_memory_stats() {
+        let stats = MemoryStats {
+            peak_bytes: 2048,
+            total_bytes: 8192,
+            allocations: 12,
+        };
+
+        let json = JsonValue::from(&stats).stringify().unwrap();
+        let parsed = MemoryStats::try_from(&json.parse::<JsonValue>().unwrap()).unwrap();
+        assert_eq!(parsed, stats);
+    }
+}
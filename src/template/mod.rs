@@ -1,59 +1,172 @@
-use std::{env, fs};
+use std::{env, fs, io, process::Command};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod inputs;
+pub mod memory;
+pub mod registry;
 pub mod runner;
+pub mod submissions;
 
+pub use check::Threshold;
 pub use day::*;
+pub use puzzle::*;
+pub use run_multi::DEFAULT_TIMEOUT;
+pub use year::*;
 
+mod check;
+mod cpu_profile;
 mod day;
+mod examples;
+mod history;
+mod integrity;
+mod progress;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Variables that `cargo run` sets for the template itself, by name or prefix.
+const CARGO_RUN_ENV: [&str; 7] = [
+    "CARGO_MANIFEST_",
+    "CARGO_PKG_",
+    "CARGO_CRATE_NAME",
+    "CARGO_BIN_NAME",
+    "CARGO_PRIMARY_PACKAGE",
+    "CARGO_TARGET_TMPDIR",
+    "OUT_DIR",
+];
+
+/// A `cargo` command to build or run the solutions from within a command of the template.
+///
+/// Removes the variables that the outer `cargo run` sets for the template. Build scripts of
+/// dependencies track some of them, e.g. `ring` tracks `CARGO_MANIFEST_DIR` and `OUT_DIR`, so
+/// they would otherwise be rebuilt whenever a nested and a direct build alternate.
+#[must_use]
+pub fn cargo_command() -> Command {
+    let mut cmd = Command::new("cargo");
+    for (key, _) in env::vars_os() {
+        let is_cargo_run_env = key
+            .to_str()
+            .is_some_and(|key| CARGO_RUN_ENV.iter().any(|name| key.starts_with(name)));
+        if is_cargo_run_env {
+            cmd.env_remove(key);
+        }
+    }
+    cmd
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_file(folder, puzzle).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error instead of panicking.
+pub fn try_read_file(folder: &str, puzzle: PuzzleId) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    fs::read_to_string(cwd.join(puzzle.data_path(folder, "txt")))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(puzzle.data_dir(folder))
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `PUZZLE` and sets up the input and runner for each part.
+///
+/// The first two parameters are the year and day of the puzzle. The optional, third parameter
+/// (1 or 2) allows you to only run a single part of the solution.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional `parser = <fn>` parameter sets up a parse step: the parser receives the input and
+/// its output is shared by both parts, which take a reference to it. The parse step is timed
+/// separately from the parts.
+///
+/// Also exposes the constant `SOLUTION`, which registers the day with the in-process runner,
+/// and an ignored test that checks the solution against the answers stored in `data/{year}/answers`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr, parser = $parser:expr) => {
+        $crate::solution!(@impl $year, $day, [$parser], [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, 1, parser = $parser:expr) => {
+        $crate::solution!(@impl $year, $day, [$parser], [part_one, 1]);
+    };
+    ($year:expr, $day:expr, 2, parser = $parser:expr) => {
+        $crate::solution!(@impl $year, $day, [$parser], [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, [], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [], [part_one, 1]);
     };
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [], [part_two, 2]);
+    };
+
+    // without a parser, the parts receive the input itself.
+    // `@parse_step` additionally times the parser with `$step`, which returns a `ParseResult`.
+    (@parse $input:ident, []) => {
+        $input
+    };
+    (@parse $input:ident, [$parser:expr]) => {
+        $parser($input)
+    };
+    (@parse_step $input:ident, $step:ident($($arg:expr),*), []) => {
+        ($input, None)
+    };
+    (@parse_step $input:ident, $step:ident($($arg:expr),*), [$parser:expr]) => {{
+        let (parsed, result) = $step($parser, $input $(, $arg)*);
+        (parsed, Some(result))
+    }};
+
+    (@arg $parsed:ident, []) => {
+        $parsed
+    };
+    (@arg $parsed:ident, [$parser:expr]) => {
+        &$parsed
+    };
+
+    (@impl $year:expr, $day:expr, $parser:tt, $( [$func:expr, $part:expr] )*) => {
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::puzzle!($year, $day);
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        #[allow(dead_code)]
+        fn __solve(input: &str, is_timed: bool) -> $crate::template::runner::SolutionResult {
+            use $crate::template::runner::*;
+            let (parsed, parse) =
+                $crate::solution!(@parse_step input, solve_parse(is_timed), $parser);
+            SolutionResult {
+                parse,
+                parts: vec![$(
+                    solve_part($func, $crate::solution!(@arg parsed, $parser), $part, is_timed),
+                )*],
+            }
+        }
+
+        /// Entry point for the in-process runner.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                puzzle: PUZZLE,
+                solve: __solve,
+            };
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
@@ -61,8 +174,34 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            if std::env::args().any(|x| x == "--all-inputs") {
+                $crate::template::inputs::run_all(&SOLUTION);
+                return;
+            }
+            let input = read_input(PUZZLE);
+            let input = input.as_str();
+            let part = selected_part(&[$($part),*]);
+            let submit = submitted_part(&[$($part),*], part);
+            let (parsed, _): (_, Option<ParseResult>) =
+                $crate::solution!(@parse_step input, run_parse(), $parser);
+            $(
+                if part.is_none_or(|part| part == $part) {
+                    run_part($func, $crate::solution!(@arg parsed, $parser), PUZZLE, $part, submit == Some($part));
+                }
+            )*
+        }
+
+        #[cfg(test)]
+        #[test]
+        #[ignore = "runs the solution against the real puzzle input"]
+        fn matches_stored_answers() {
+            let Ok(input) = $crate::template::try_read_file("inputs", PUZZLE) else {
+                eprintln!("No puzzle input for {PUZZLE}.");
+                return;
+            };
+            let input = input.as_str();
+            let parsed = $crate::solution!(@parse input, $parser);
+            $( $crate::template::answers::assert_answer(PUZZLE, $part, $func($crate::solution!(@arg parsed, $parser))); )*
         }
     };
 }
@@ -0,0 +1,150 @@
+/// Tracks earned stars offline, based on the data stored for each puzzle.
+///
+/// A part counts as solved if a correct submission is logged in `data/{year}/submissions`, or if its
+/// answer is stored in `data/{year}/answers`. Answers of puzzles solved elsewhere can be stored
+/// with `cargo solve <day> --record`.
+use crate::template::answers::Answers;
+use crate::template::submissions::{Outcome, SubmissionLog};
+use crate::template::{all_puzzles, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// The earned stars of a single day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayStars {
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+impl DayStars {
+    pub fn count(self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+}
+
+/// The earned stars of all days of a year, starting with day 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    pub year: Year,
+    pub days: Vec<DayStars>,
+}
+
+impl Progress {
+    /// Collect the stars of a year from the stored submissions and answers.
+    pub fn read(year: Year) -> Self {
+        Progress::from_puzzles(year, |puzzle| {
+            let log = SubmissionLog::read(puzzle);
+            let answers = Answers::read(puzzle);
+
+            let is_solved = |part: u8| {
+                answers.get(part).is_some()
+                    || log.get(part).iter().any(|s| s.outcome == Outcome::Correct)
+            };
+
+            DayStars {
+                part_1: is_solved(1),
+                part_2: is_solved(2),
+            }
+        })
+    }
+
+    /// Collect the stars of a year, `stars` returns the stars of a single puzzle.
+    pub fn from_puzzles(year: Year, stars: impl Fn(PuzzleId) -> DayStars) -> Self {
+        let mut progress = Progress {
+            year,
+            days: all_puzzles(year).map(stars).collect(),
+        };
+
+        // the second star of the last day is awarded for collecting all other stars.
+        let is_missing_last_star = progress.stars() + 1 == progress.max_stars();
+        if let Some(last) = progress.days.last_mut() {
+            if is_missing_last_star && !last.part_2 {
+                last.part_2 = true;
+            }
+        }
+
+        progress
+    }
+
+    pub fn stars(&self) -> usize {
+        self.days.iter().map(|d| d.count()).sum()
+    }
+
+    pub fn max_stars(&self) -> usize {
+        self.days.len() * 2
+    }
+
+    /// Print a calendar of the earned stars, five days per row.
+    pub fn print_calendar(&self) {
+        println!(
+            "{ANSI_BOLD}{} Progress:{ANSI_RESET} {}/{} ⭐",
+            self.year,
+            self.stars(),
+            self.max_stars()
+        );
+        println!();
+
+        for (row, days) in self.days.chunks(5).enumerate() {
+            let cells: Vec<String> = days
+                .iter()
+                .enumerate()
+                .map(|(i, stars)| format!("{:>2} {}", row * 5 + i + 1, format_stars(*stars)))
+                .collect();
+            println!("{}", cells.join("   "));
+        }
+    }
+}
+
+/// Earned stars as `★`, missing ones as `☆`.
+fn format_stars(stars: DayStars) -> String {
+    [stars.part_1, stars.part_2]
+        .iter()
+        .map(|earned| if *earned { '★' } else { '☆' })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_stars, DayStars, Progress};
+    use crate::year;
+
+    fn stars(part_1: bool, part_2: bool) -> DayStars {
+        DayStars { part_1, part_2 }
+    }
+
+    #[test]
+    fn counts_stars() {
+        let progress = Progress::from_puzzles(year!(2023), |puzzle| {
+            let day = puzzle.day.into_inner();
+            stars(day <= 10, day <= 5)
+        });
+
+        assert_eq!(progress.days.len(), 25);
+        assert_eq!(progress.stars(), 15);
+        assert_eq!(progress.max_stars(), 50);
+        assert_eq!(progress.days[4], stars(true, true));
+        assert_eq!(progress.days[5], stars(true, false));
+        assert_eq!(progress.days[24], stars(false, false));
+    }
+
+    #[test]
+    fn awards_last_star() {
+        let progress = Progress::from_puzzles(year!(2023), |puzzle| {
+            stars(true, puzzle.day.into_inner() != 25)
+        });
+        assert_eq!(progress.stars(), 50);
+
+        let progress = Progress::from_puzzles(year!(2023), |puzzle| {
+            let day = puzzle.day.into_inner();
+            stars(day != 24, day != 25)
+        });
+        assert_eq!(progress.stars(), 48);
+    }
+
+    #[test]
+    fn formats_stars() {
+        assert_eq!(format_stars(stars(true, true)), "★★");
+        assert_eq!(format_stars(stars(true, false)), "★☆");
+        assert_eq!(format_stars(stars(false, false)), "☆☆");
+    }
+}
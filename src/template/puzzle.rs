@@ -0,0 +1,103 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::template::config::config;
+use crate::template::{all_days, Day, Year};
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `{year}-{day}`, which is also the name of the puzzle's binary.
+///
+/// ```
+/// # use advent_of_code::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2023).unwrap(), Day::new(1).unwrap());
+/// assert_eq!(puzzle.to_string(), "2023-01")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of a data file of this puzzle, e.g. `data/2023/inputs/01.txt`.
+    #[must_use]
+    pub fn data_path(&self, folder: &str, extension: &str) -> PathBuf {
+        self.data_dir(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+
+    /// Directory holding the data files of this puzzle's year, e.g. `data/2023/inputs`.
+    #[must_use]
+    pub fn data_dir(&self, folder: &str) -> PathBuf {
+        config().data_dir(self.year, folder)
+    }
+
+    /// Path of this puzzle's solution, e.g. `src/bin/2023-01.rs`.
+    /// Uses `/` on every platform, so that it can also be used in links of the readme.
+    #[must_use]
+    pub fn bin_path(&self) -> PathBuf {
+        PathBuf::from(format!("src/bin/{self}.rs"))
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the puzzle of today, if the event is currently running.
+    pub fn today() -> Option<Self> {
+        Some(Self::new(Year::today()?, Day::today()?))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/// Returns an iterator over all puzzles of a year.
+pub fn all_puzzles(year: Year) -> impl Iterator<Item = PuzzleId> {
+    all_days().map(move |day| PuzzleId::new(year, day))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {
+        $crate::template::PuzzleId::new($crate::year!($year), $crate::day!($day))
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::all_puzzles;
+    use crate::{day, year};
+
+    #[test]
+    fn builds_paths() {
+        let puzzle = puzzle!(2023, 1);
+        assert_eq!(
+            puzzle.data_path("inputs", "txt"),
+            PathBuf::from("data/2023/inputs/01.txt")
+        );
+        assert_eq!(puzzle.bin_path(), PathBuf::from("src/bin/2023-01.rs"));
+    }
+
+    #[test]
+    fn iterates_puzzles_of_a_year() {
+        let mut iter = all_puzzles(year!(2022));
+        assert_eq!(iter.next(), Some(puzzle!(2022, 1)));
+        assert_eq!(iter.last().map(|p| p.day), Some(day!(25)));
+    }
+}
@@ -1,11 +1,19 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// Tables are written between pairs of markers, a file can contain several of them. The start marker
+/// can carry options, e.g. `<!--- benchmarking table year=2023 columns=parse,part_1,change --->`.
+/// See [`TableOptions`] for the available options.
+use std::{fmt::Display, fs, io, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::config::config;
+use crate::template::memory::{format_bytes, MemoryStats};
+use crate::template::timings::{Timing, Timings};
+use crate::template::Year;
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_START: &str = "<!--- benchmarking table";
+static MARKER_END: &str = "--->";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -20,60 +28,295 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => f.write_str(e),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A column of the benchmark table, after the day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Duration of the parse step.
+    Parse,
+    /// Duration of part one.
+    Part1,
+    /// Duration of part two.
+    Part2,
+    /// Number of benchmark samples of each part.
+    Samples,
+    /// Peak heap usage of each part, see the `memory-stats` feature.
+    Memory,
+    /// Change of the total duration since the previous stored run.
+    Change,
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Samples => "Samples",
+            Column::Memory => "Peak memory",
+            Column::Change => "Change",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "samples" => Ok(Column::Samples),
+            "memory" => Ok(Column::Memory),
+            "change" => Ok(Column::Change),
+            _ => Err(format!(
+                "unknown column `{s}`, expected one of `parse`, `part_1`, `part_2`, `samples`, `memory`, `change`."
+            )),
+        }
+    }
+}
+
+/// Options of a table, parsed from its start marker.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableOptions {
+    /// `heading=###`: markdown heading of the title, `none` omits the title. Defaults to `##`.
+    pub heading: Option<String>,
+    /// `title="2023 Benchmarks"`: title of the table. Defaults to `Benchmarks`.
+    pub title: String,
+    /// `year=2023`: only list the puzzles of a year.
+    pub year: Option<Year>,
+    /// `columns=parse,part_1,part_2`: columns after the day.
+    /// Defaults to the parse step and both parts, plus the peak memory if it was recorded.
+    pub columns: Option<Vec<Column>>,
+    /// `highlight=3`: number of slowest days to highlight.
+    pub highlight: usize,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        TableOptions {
+            heading: Some("##".into()),
+            title: "Benchmarks".into(),
+            year: None,
+            columns: None,
+            highlight: 0,
+        }
+    }
+}
+
+impl FromStr for TableOptions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = TableOptions::default();
+
+        for token in split_options(s)? {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or(format!("expected option `{token}` to be `key=value`."))?;
+
+            match key {
+                "heading" if value == "none" => options.heading = None,
+                "heading" => options.heading = Some(value.into()),
+                "title" => options.title = value.into(),
+                "year" => {
+                    options.year = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid year `{value}`."))?,
+                    );
+                }
+                "columns" => {
+                    options.columns = Some(
+                        value
+                            .split(',')
+                            .map(|c| c.trim().parse())
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                "highlight" => {
+                    options.highlight = value
+                        .parse()
+                        .map_err(|_| format!("invalid number of days to highlight `{value}`."))?;
+                }
+                _ => return Err(format!("unknown option `{key}`.")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Split options at whitespace, keeping quoted values like `title="2023 Benchmarks"` together.
+fn split_options(s: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut in_quotes = false;
+
+    for c in s.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("unterminated quote in table options.".into());
+    }
+
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
+    /// The start marker, which is kept as-is when the table is replaced.
+    marker: String,
+    options: TableOptions,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
+fn locate_tables(readme: &str) -> Result<Vec<TablePosition>, Error> {
+    let mut markers: Vec<(usize, usize)> = vec![];
+    let mut offset = 0;
+
+    while let Some(start) = readme[offset..].find(MARKER_START).map(|i| i + offset) {
+        let end = readme[start..]
+            .find(MARKER_END)
+            .map(|i| i + start + MARKER_END.len())
+            .ok_or_else(|| Error::Parser("Unterminated benchmarking table marker.".into()))?;
+        markers.push((start, end));
+        offset = end;
+    }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+    if markers.is_empty() {
+        return Err(Error::Parser("Could not find table start position.".into()));
+    }
 
-    if matches.len() > 2 {
+    if !markers.len().is_multiple_of(2) {
         return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+            "Could not find table end position, markers have to come in pairs.".into(),
         ));
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
-
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    markers
+        .chunks(2)
+        .map(|pair| {
+            let marker = &readme[pair[0].0..pair[0].1];
+            let options = marker[MARKER_START.len()..marker.len() - MARKER_END.len()]
+                .parse()
+                .map_err(Error::Parser)?;
 
-    Ok(TablePosition { pos_start, pos_end })
+            Ok(TablePosition {
+                pos_start: pair[0].0,
+                pos_end: pair[1].1,
+                marker: marker.into(),
+                options,
+            })
+        })
+        .collect()
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(table: &TablePosition, timings: &Timings, previous: &Timings) -> String {
+    let options = &table.options;
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let timings: Vec<&Timing> = timings
+        .data
+        .iter()
+        .filter(|t| options.year.is_none_or(|year| t.puzzle.year == year))
+        .collect();
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    let columns = options.columns.clone().unwrap_or_else(|| {
+        let mut columns = vec![Column::Parse, Column::Part1, Column::Part2];
+        // the peak memory of each part is only known if the timings were collected with `memory-stats`.
+        if timings
+            .iter()
+            .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some())
+        {
+            columns.push(Column::Memory);
+        }
+        columns
+    });
+
+    let mut lines: Vec<String> = vec![table.marker.clone()];
+
+    if let Some(heading) = &options.heading {
+        lines.push(format!("{heading} {}", options.title));
+        lines.push(String::new());
     }
 
+    lines.push(format!(
+        "| Day | {} |",
+        columns
+            .iter()
+            .map(|c| c.header())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    ));
+    lines.push(format!("|{}", " :---: |".repeat(columns.len() + 1)));
+
+    // only mention years if the benchmarks span more than one of them.
+    let is_multi_year = timings
+        .windows(2)
+        .any(|w| w[0].puzzle.year != w[1].puzzle.year);
+
+    let mut slowest: Vec<&Timing> = timings.clone();
+    slowest.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    slowest.truncate(options.highlight);
+
+    for timing in &timings {
+        let path = format!("./{}", timing.puzzle.bin_path().display());
+        let label = if is_multi_year {
+            format!(
+                "{} Day {}",
+                timing.puzzle.year,
+                timing.puzzle.day.into_inner()
+            )
+        } else {
+            format!("Day {}", timing.puzzle.day.into_inner())
+        };
+
+        let link = if slowest.iter().any(|t| t.puzzle == timing.puzzle) {
+            format!("**[{label}]({path})**")
+        } else {
+            format!("[{label}]({path})")
+        };
+
+        let before = previous.data.iter().find(|t| t.puzzle == timing.puzzle);
+
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| format_cell(*column, timing, before))
+            .collect();
+
+        lines.push(format!("| {link} | {} |", cells.join(" | ")));
+    }
+
+    let totals: Vec<String> = columns
+        .iter()
+        .map(|column| format_total(*column, &timings))
+        .collect();
+    lines.push(format!("| **Total** | {} |", totals.join(" | ")));
+
+    let total_millis = timings.iter().map(|t| t.total_nanos).sum::<f64>() / 1_000_000_f64;
+
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
@@ -81,78 +324,183 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn format_cell(column: Column, timing: &Timing, before: Option<&Timing>) -> String {
+    let code = |value: Option<String>| format!("`{}`", value.unwrap_or_else(|| "-".into()));
+
+    let samples = |part: u8| {
+        let stats = match part {
+            1 => timing.part_1_stats.as_ref(),
+            _ => timing.part_2_stats.as_ref(),
+        };
+        code(stats.map(|s| s.samples.to_string()))
+    };
+
+    let peak = |memory: Option<&MemoryStats>| code(memory.map(|m| format_bytes(m.peak_bytes)));
+
+    match column {
+        Column::Parse => code(timing.parse.clone()),
+        Column::Part1 => code(timing.part_1.clone()),
+        Column::Part2 => code(timing.part_2.clone()),
+        Column::Samples => format!("{} / {}", samples(1), samples(2)),
+        Column::Memory => format!(
+            "{} / {}",
+            peak(timing.part_1_memory.as_ref()),
+            peak(timing.part_2_memory.as_ref())
+        ),
+        Column::Change => code(
+            before
+                .filter(|before| before.total_nanos > 0.0)
+                .map(|before| {
+                    let percent =
+                        (timing.total_nanos - before.total_nanos) / before.total_nanos * 100.0;
+                    format!("{percent:+.1}%")
+                }),
+        ),
+    }
+}
+
+/// Sum of a duration column. Other columns have no total.
+fn format_total(column: Column, timings: &[&Timing]) -> String {
+    let total = |duration: &dyn Fn(&Timing) -> Option<Duration>| {
+        let durations: Vec<Duration> = timings.iter().filter_map(|t| duration(t)).collect();
+        if durations.is_empty() {
+            "`-`".into()
+        } else {
+            format!("`{:.1?}`", durations.iter().sum::<Duration>())
+        }
+    };
+
+    match column {
+        Column::Parse => total(&Timing::parse_duration),
+        Column::Part1 => total(&|t| t.part_duration(1)),
+        Column::Part2 => total(&|t| t.part_duration(2)),
+        Column::Samples | Column::Memory | Column::Change => String::new(),
+    }
+}
+
+fn update_content(s: &mut String, timings: &Timings, previous: &Timings) -> Result<(), Error> {
+    // NOTE: replace the last table first, so that the positions of the others stay valid.
+    for table in locate_tables(s)?.iter().rev() {
+        let content = construct_table(table, timings, previous);
+        s.replace_range(table.pos_start..table.pos_end, &content);
+    }
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+/// Update the benchmark tables of all files listed in `readme.benchmarks` of `aoc.toml`.
+/// `previous` holds the timings of the previous run of each puzzle, for the `change` column.
+pub fn update(timings: &Timings, previous: &Timings) -> Result<(), Error> {
+    for path in &config().readme.benchmarks {
+        let mut content = String::from_utf8_lossy(&fs::read(path)?).to_string();
+        update_content(&mut content, timings, previous).map_err(|e| match e {
+            Error::Parser(e) => Error::Parser(format!("{}: {e}", path.display())),
+            e @ Error::IO(_) => e,
+        })?;
+        fs::write(path, &content)?;
+    }
+
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{locate_tables, update_content, Column, TableOptions, MARKER};
+    use crate::{
+        puzzle,
+        template::memory::MemoryStats,
+        template::runner::BenchStats,
+        template::timings::{Timing, Timings},
+        year,
+    };
+    use std::time::Duration;
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    total_nanos: 3e+7,
                 },
                 Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2023, 2),
+                    parse: Some("5ms".into()),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    total_nanos: 7.5e+7,
                 },
                 Timing {
-                    day: day!(4),
+                    puzzle: puzzle!(2023, 4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    total_nanos: 9e+7,
                 },
             ],
         }
     }
 
+    fn update(s: &mut String, timings: Timings) {
+        update_content(s, &timings, &Timings::default()).unwrap();
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
+    }
+
+    #[test]
+    fn ignores_markers_mentioned_in_prose() {
+        let s = format!(
+            "{MARKER}{MARKER}\n\nThe table is written between two `benchmarking table` comments."
+        );
+        assert_eq!(locate_tables(&s).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn locates_the_table_of_the_readme() {
+        let readme = include_str!("../../README.md");
+        assert_eq!(locate_tables(readme).unwrap().len(), 1);
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
+        update(&mut s, get_mock_timings());
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,24 +508,129 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40ms` | `50ms` |",
+            "| **Total** | `5.0ms` | `80.0ms` | `110.0ms` |",
             "",
-            "**Total: 190.00ms**",
+            "**Total: 195.00ms**",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_of_multiple_years() {
+        let mut timings = get_mock_timings();
+        timings.data[0].puzzle = puzzle!(2022, 1);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update(&mut s, timings);
+        assert!(s.contains("| [2022 Day 1](./src/bin/2022-01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [2023 Day 2](./src/bin/2023-02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_1_memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            allocations: 2,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update(&mut s, timings);
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 | Peak memory |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` | `-` / `-` |"));
+        assert!(s.contains(
+            "| [Day 2](./src/bin/2023-02.rs) | `5ms` | `30ms` | `40ms` | `2.0 KiB` / `-` |"
+        ));
+    }
+
+    #[test]
+    fn parses_table_options() {
+        let options: TableOptions =
+            " year=2023 heading=### title=\"2023 Benchmarks\" columns=part_1,change highlight=2 "
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            options,
+            TableOptions {
+                heading: Some("###".into()),
+                title: "2023 Benchmarks".into(),
+                year: Some(year!(2023)),
+                columns: Some(vec![Column::Part1, Column::Change]),
+                highlight: 2,
+            }
+        );
+        assert_eq!("".parse::<TableOptions>().unwrap(), TableOptions::default());
+        assert!("columns=part_3".parse::<TableOptions>().is_err());
+        assert!("title=\"oops".parse::<TableOptions>().is_err());
+        assert!("foo=bar".parse::<TableOptions>().is_err());
+    }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(BenchStats::from_samples(&[Duration::from_millis(10)]));
+
+        let mut previous = get_mock_timings();
+        previous.data[1].total_nanos = 6e+7;
+
+        let mut s = format!(
+            "<!--- benchmarking table heading=none columns=part_1,samples,change highlight=1 --->\n{MARKER}"
+        );
+        update_content(&mut s, &timings, &previous).unwrap();
+
+        let expected = [
+            "<!--- benchmarking table heading=none columns=part_1,samples,change highlight=1 --->",
+            "| Day | Part 1 | Samples | Change |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10ms` | `1` / `-` | `+0.0%` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30ms` | `-` / `-` | `+25.0%` |",
+            "| **[Day 4](./src/bin/2023-04.rs)** | `40ms` | `-` / `-` | `+0.0%` |",
+            "| **Total** | `80.0ms` |  |  |",
+            "",
+            "**Total: 195.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_per_year() {
+        let mut timings = get_mock_timings();
+        timings.data[0].puzzle = puzzle!(2022, 1);
+
+        let mut s = format!(
+            "<!--- benchmarking table year=2022 title=\"2022 Benchmarks\" --->{MARKER}\n\n\
+             <!--- benchmarking table year=2023 --->{MARKER}"
+        );
+        update(&mut s, timings.clone());
+        update(&mut s, timings);
+
+        let (first, second) = s
+            .split_once("\n\n<!--- benchmarking table year=2023")
+            .unwrap();
+        assert!(first.contains("## 2022 Benchmarks"));
+        assert!(first.contains("| [Day 1](./src/bin/2022-01.rs) |"));
+        assert!(!first.contains("Day 2"));
+        assert!(second.contains("| [Day 2](./src/bin/2023-02.rs) |"));
+        assert!(second.contains("**Total: 165.00ms**"));
+        assert!(!second.contains("Day 1"));
+    }
 }
@@ -0,0 +1,115 @@
+/// Module that updates the stars table of the readme, the offline counterpart of the `readme-stars` workflow.
+/// Writes the same table as `aoc-readme-stars`, so both can be used interchangeably.
+use std::fs;
+
+use crate::template::config::config;
+use crate::template::progress::Progress;
+use crate::template::readme_benchmarks::Error;
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+fn construct_table(progress: &Progress) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {} Results", progress.year),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |earned: bool| if earned { "⭐" } else { " " };
+
+    // only list days with at least one star.
+    for (i, stars) in progress
+        .days
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.count() > 0)
+    {
+        let day = i + 1;
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{}/day/{day}) | {} | {} |",
+            progress.year,
+            star(stars.part_1),
+            star(stars.part_2)
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &Progress) -> Result<(), Error> {
+    let matches: Vec<_> = s.match_indices(MARKER).map(|m| m.0).collect();
+
+    let [start, end] = matches[..] else {
+        return Err(Error::Parser(format!(
+            "expected exactly two `{MARKER}` markers in README."
+        )));
+    };
+
+    s.replace_range(start..end + MARKER.len(), &construct_table(progress));
+    Ok(())
+}
+
+pub fn update(progress: &Progress) -> Result<(), Error> {
+    let path = &config().readme.stars;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::template::progress::{DayStars, Progress};
+    use crate::year;
+
+    fn get_mock_progress() -> Progress {
+        Progress::from_puzzles(year!(2023), |puzzle| {
+            let day = puzzle.day.into_inner();
+            DayStars {
+                part_1: day <= 2,
+                part_2: day == 1,
+            }
+        })
+    }
+
+    #[test]
+    fn updates_stars() {
+        let mut s = format!("foo\n{MARKER}\n## 2023 Results\n{MARKER}\nbar");
+        update_content(&mut s, &get_mock_progress()).unwrap();
+
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_the_table_of_the_readme() {
+        let mut s = include_str!("../../README.md").to_string();
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.contains("| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |"));
+    }
+
+    #[test]
+    fn errors_without_markers() {
+        let mut s = format!("foo\n{MARKER}");
+        assert!(update_content(&mut s, &get_mock_progress()).is_err());
+    }
+}
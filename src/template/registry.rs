@@ -0,0 +1,79 @@
+/// Library-level registry of solutions that can be run in-process.
+///
+/// Every `solution!` invocation exposes a `SOLUTION` constant. With the `all` feature, the main
+/// binary compiles all scaffolded puzzles as modules (see `build.rs`) and collects these constants
+/// into a [`Registry`], which allows `cargo all` to call each day's parts directly instead of
+/// spawning `cargo run`.
+use crate::template::runner::SolutionResult;
+use crate::template::PuzzleId;
+
+/// Type-erased entry point of a day's solution.
+/// Receives the puzzle input and whether the parts should be benched.
+pub type SolveFn = fn(&str, bool) -> SolutionResult;
+
+/// A registered solution for a single puzzle.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub solve: SolveFn,
+}
+
+/// A set of registered solutions, at most one per puzzle.
+#[derive(Clone, Default)]
+pub struct Registry {
+    solutions: Vec<Solution>,
+}
+
+impl Registry {
+    pub fn new(mut solutions: Vec<Solution>) -> Self {
+        solutions.sort_unstable_by_key(|s| s.puzzle);
+        solutions.dedup_by_key(|s| s.puzzle);
+        Self { solutions }
+    }
+
+    /// Returns the solution registered for `puzzle`, if any.
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&Solution> {
+        self.solutions.iter().find(|s| s.puzzle == puzzle)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Registry, Solution};
+    use crate::puzzle;
+    use crate::template::runner::SolutionResult;
+
+    fn solve(_: &str, _: bool) -> SolutionResult {
+        SolutionResult::default()
+    }
+
+    #[test]
+    fn looks_up_solutions_by_puzzle() {
+        let registry = Registry::new(vec![
+            Solution {
+                puzzle: puzzle!(2023, 3),
+                solve,
+            },
+            Solution {
+                puzzle: puzzle!(2023, 1),
+                solve,
+            },
+        ]);
+
+        assert!(registry.get(puzzle!(2023, 1)).is_some());
+        assert!(registry.get(puzzle!(2023, 3)).is_some());
+        assert!(registry.get(puzzle!(2023, 2)).is_none());
+        assert!(registry.get(puzzle!(2022, 1)).is_none());
+    }
+
+    #[test]
+    fn handles_empty_registry() {
+        let registry = Registry::default();
+        assert!(registry.is_empty());
+        assert!(registry.get(puzzle!(2023, 1)).is_none());
+    }
+}
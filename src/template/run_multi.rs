@@ -1,56 +1,403 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
+    answers::{AnswerCheck, Answers},
+    integrity::{self, InputError},
+    registry::Registry,
+    runner::{print_parse_result, print_part_result, SolutionResult},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Default wall-clock limit for running a single puzzle.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Determines how the solutions of each puzzle are invoked.
+pub enum Runner<'a> {
+    /// Call the registered solutions directly, in the current process.
+    InProcess(&'a Registry),
+    /// Spawn each puzzle's binary as a child process. Keeps benchmarks of different puzzles isolated.
+    ChildProcess { is_release: bool },
+}
+
+/// Overall result of a puzzle, as listed in the summary of [`run_multi`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// All parts returned an answer and no answer contradicts a stored one.
+    Solved,
+    /// At least one part did not return an answer yet.
+    Unsolved,
+    /// A part returned an error or a wrong answer, or the solution panicked.
+    Failed(String),
+    TimedOut,
+    NotScaffolded,
+}
+
+impl Status {
+    fn from_result(result: &SolutionResult, answers: &Answers) -> Self {
+        let mut reasons = vec![];
+
+        for part in &result.parts {
+            if let Some(e) = &part.error {
+                reasons.push(format!("part {}: {e}", part.part));
+            } else if let AnswerCheck::Mismatch { .. } =
+                answers.check(part.part, part.answer.as_deref())
+            {
+                reasons.push(format!("part {}: wrong answer", part.part));
+            }
+        }
+
+        if !reasons.is_empty() {
+            Status::Failed(reasons.join(", "))
+        } else if result.parts.is_empty() || result.parts.iter().any(|p| p.answer.is_none()) {
+            Status::Unsolved
+        } else {
+            Status::Solved
+        }
+    }
+
+    /// Whether the status should make the overall run fail.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Failed(_) | Status::TimedOut)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Solved => f.write_str("solved"),
+            Status::Unsolved => f.write_str("unsolved"),
+            Status::Failed(_) => f.write_str("failed"),
+            Status::TimedOut => f.write_str("timed out"),
+            Status::NotScaffolded => f.write_str("not scaffolded"),
+        }
+    }
+}
+
+/// A line that a solution printed besides its results.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OutputLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl OutputLine {
+    fn print(&self) {
+        match self {
+            OutputLine::Stdout(line) => println!("{line}"),
+            OutputLine::Stderr(line) => eprintln!("{line}"),
+        }
+    }
+}
+
+/// Destination of the output of a solution: printed as it happens, or captured to be printed later.
+#[derive(Clone, Default)]
+pub struct Output(Option<Arc<Mutex<Vec<OutputLine>>>>);
+
+impl Output {
+    /// Output that is collected instead of printed, see [`Output::into_lines`].
+    pub fn captured() -> Self {
+        Output(Some(Arc::default()))
+    }
+
+    pub fn is_captured(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn write(&self, line: OutputLine) {
+        match &self.0 {
+            Some(lines) => lines.lock().unwrap().push(line),
+            None => line.print(),
+        }
+    }
+
+    /// The captured lines, in the order they were written. Empty if the output was printed.
+    pub fn into_lines(self) -> Vec<OutputLine> {
+        self.0
+            .map(|lines| std::mem::take(&mut *lines.lock().unwrap()))
+            .unwrap_or_default()
+    }
+}
+
+/// Result of [`run_multi`]: the status of every puzzle and, if timed, their timings.
+#[derive(Debug)]
+pub struct Summary {
+    pub statuses: Vec<(PuzzleId, Status)>,
+    pub timings: Option<Timings>,
+}
+
+impl Summary {
+    pub fn is_success(&self) -> bool {
+        !self.statuses.iter().any(|(_, status)| status.is_failure())
+    }
+
+    fn print(&self) {
+        println!("\n{ANSI_BOLD}Summary{ANSI_RESET}\n");
+        println!("| {:<7} | {:<14} | Details", "Day", "Status");
+        println!("|{:-<9}|{:-<16}|{:-<9}", "", "", "");
+
+        for (puzzle, status) in &self.statuses {
+            let details = match status {
+                Status::Failed(reason) => reason.as_str(),
+                _ => "",
+            };
+            println!(
+                "| {:<7} | {:<14} | {details}",
+                puzzle.to_string(),
+                status.to_string()
+            );
+        }
+    }
+}
+
+/// Run the solutions of a set of puzzles and print a summary.
+/// A puzzle that does not finish within `timeout` is abandoned.
+///
+/// With `jobs` > 1, up to `jobs` puzzles run at the same time. Their results are still printed in order,
+/// together with the output of their solutions, which is captured until the puzzle is reported. Only
+/// [`Runner::ChildProcess`] can capture that output, solutions that run in-process print it as it happens.
+pub fn run_multi(
+    puzzles_to_run: &HashSet<PuzzleId>,
+    runner: &Runner,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Summary {
+    let mut timings: Vec<Timing> = Vec::with_capacity(puzzles_to_run.len());
+    let mut statuses: Vec<(PuzzleId, Status)> = Vec::with_capacity(puzzles_to_run.len());
 
     let mut need_space = false;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+    // NOTE: use non-duplicate, sorted puzzle values.
+    let mut puzzles: Vec<PuzzleId> = puzzles_to_run.iter().copied().collect();
+    puzzles.sort_unstable();
+
+    let run = |puzzle: PuzzleId| {
+        let output = if jobs > 1 {
+            Output::captured()
+        } else {
+            Output::default()
+        };
+
+        let result = match runner {
+            Runner::InProcess(registry) => run_in_process(registry, puzzle, is_timed, timeout),
+            Runner::ChildProcess { is_release } => {
+                child_commands::run_solution(puzzle, is_timed, *is_release, timeout, &output)
             }
-            need_space = true;
+        };
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        (result, output.into_lines())
+    };
+
+    let mut print_header = |puzzle: PuzzleId| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}{} Day {}{ANSI_RESET}", puzzle.year, puzzle.day);
+        println!("-----------");
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut report = |puzzle: PuzzleId, result: Result<SolutionResult, Error>| {
+        let status = match result {
+            Ok(result) => {
+                let answers = Answers::read(puzzle);
+
+                if result.parts.is_empty() {
+                    println!("Not solved.");
+                } else {
+                    if let Some(parse) = &result.parse {
+                        print_parse_result(parse);
+                    }
+                    result.parts.iter().for_each(|part| {
+                        print_part_result(part, &answers.check(part.part, part.answer.as_deref()));
+                    });
+                    timings.push(Timing::from_results(puzzle, &result));
+                }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                Status::from_result(&result, &answers)
+            }
+            Err(Error::NotScaffolded) => {
+                println!("Not scaffolded.");
+                Status::NotScaffolded
+            }
+            Err(Error::TimedOut) => {
+                let timeout = timeout.unwrap_or_default();
+                println!("Timed out after {timeout:.0?}.");
+                Status::TimedOut
             }
+            Err(e) => {
+                println!("Failed: {e}");
+                Status::Failed(e.to_string())
+            }
+        };
+
+        statuses.push((puzzle, status));
+    };
+
+    if jobs > 1 {
+        run_parallel(&puzzles, jobs, run, |puzzle, (result, output)| {
+            print_header(puzzle);
+            output.iter().for_each(OutputLine::print);
+            report(puzzle, result);
         });
+    } else {
+        for puzzle in puzzles {
+            // print the header first, so that it is visible which puzzle is running.
+            print_header(puzzle);
+            report(puzzle, run(puzzle).0);
+        }
+    }
 
-    if is_timed {
+    let timings = is_timed.then(|| {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
-    }
+        timings
+    });
+
+    let summary = Summary { statuses, timings };
+    summary.print();
+    summary
+}
+
+/// Run puzzles on `jobs` worker threads. `on_result` is called for every puzzle in the order of `puzzles`,
+/// as soon as the puzzle and all puzzles before it have finished.
+fn run_parallel<T: Send>(
+    puzzles: &[PuzzleId],
+    jobs: usize,
+    run: impl Fn(PuzzleId) -> T + Sync,
+    mut on_result: impl FnMut(PuzzleId, T),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(puzzles.len()) {
+            let sender = sender.clone();
+            let (next, run) = (&next, &run);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(puzzle) = puzzles.get(index) else {
+                    break;
+                };
+                if sender.send((index, run(*puzzle))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // NOTE: drop the original sender, so that the loop below ends once all workers are done.
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_report = 0;
+
+        for (index, result) in receiver {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&next_to_report) {
+                on_result(puzzles[next_to_report], result);
+                next_to_report += 1;
+            }
+        }
+    });
+}
+
+/// Run a registered solution in the current process.
+/// The solution runs on its own thread, so that a panic or a timeout does not take down the remaining puzzles.
+/// NOTE: a thread that timed out cannot be stopped and keeps running in the background until the process exits.
+fn run_in_process(
+    registry: &Registry,
+    puzzle: PuzzleId,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> Result<SolutionResult, Error> {
+    let Some(solution) = registry.get(puzzle) else {
+        return Err(Error::NotScaffolded);
+    };
+
+    let input = integrity::read_verified(puzzle)?;
+    let solve = solution.solve;
+
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name(puzzle.to_string())
+        // NOTE: match the stack size of the main thread, which solutions were written against.
+        .stack_size(8 * 1024 * 1024)
+        .spawn(move || {
+            // the panic message itself is printed by the default panic hook.
+            let result = panic::catch_unwind(AssertUnwindSafe(|| solve(&input, is_timed)));
+            let _ = sender.send(result.map_err(|e| panic_message(e.as_ref())));
+        })?;
+
+    let result = match timeout {
+        Some(timeout) => receiver
+            .recv_timeout(timeout)
+            .map_err(|_| Error::TimedOut)?,
+        None => receiver.recv().map_err(|_| Error::BrokenPipe)?,
+    };
+
+    result.map_err(Error::Panicked)
+}
+
+/// Extract the message of a panic payload, which is a `&str` or a `String` for panics with a message.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Input(InputError),
+    NotScaffolded,
+    BuildFailed,
+    Panicked(String),
+    Exited(Option<i32>),
+    TimedOut,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => f.write_str("lost connection to the solution"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Input(e) => write!(f, "input {e}"),
+            Error::NotScaffolded => f.write_str("not scaffolded"),
+            Error::BuildFailed => f.write_str("failed to build the solution"),
+            Error::Panicked(message) => write!(f, "panicked: {message}"),
+            Error::Exited(Some(code)) => write!(f, "exited with code {code}"),
+            Error::Exited(None) => f.write_str("terminated by a signal"),
+            Error::TimedOut => f.write_str("timed out"),
+        }
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(e: InputError) -> Self {
+        Error::Input(e)
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -59,47 +406,60 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing their JSON output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, Output, OutputLine};
+    use crate::template::{
+        cargo_command,
+        runner::{ParseResult, PartResult, SolutionResult},
+        PuzzleId,
+    };
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
+        path::PathBuf,
         process::{Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// A line of `--format json` output of a solution binary.
+    #[derive(Debug)]
+    pub enum Record {
+        Parse(ParseResult),
+        Part(PartResult),
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Run the solution bin for a given puzzle and collect the results of its parse step and parts.
+    /// The bin is built first, so that the timeout only applies to running the solution.
+    /// Build messages and any output of the solution that is not a record are written to `output`.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+        timeout: Option<Duration>,
+        output: &Output,
+    ) -> Result<SolutionResult, Error> {
+        // skip command invocation for puzzles that have not been scaffolded yet.
+        if !puzzle.bin_path().exists() {
+            return Err(Error::NotScaffolded);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let executable = build_solution(puzzle, is_release, output)?;
 
-        if is_release {
-            args.push("--release");
-        }
+        let mut args = vec!["--format", "json"];
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr and any output that is not a record, while collecting records.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,150 +468,296 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stderr_output = output.clone();
+        let stderr_thread = thread::spawn(move || {
+            let mut panic_message = None;
+            let mut is_panic_line = false;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                stderr_output.write(OutputLine::Stderr(line.clone()));
+                // the message follows the `thread 'main' panicked at src/bin/...` line.
+                if is_panic_line {
+                    panic_message = Some(line.clone());
+                }
+                is_panic_line = line.contains("panicked at");
             });
+
+            panic_message
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let stdout_output = output.clone();
+        let stdout_thread = thread::spawn(move || {
+            let mut result = SolutionResult::default();
 
-        thread.join().unwrap();
-        cmd.wait()?;
+            stdout
+                .lines()
+                .map_while(Result::ok)
+                .for_each(|line| match parse_record(&line) {
+                    Some(Record::Parse(parse)) => result.parse = Some(parse),
+                    Some(Record::Part(part)) => result.parts.push(part),
+                    None => stdout_output.write(OutputLine::Stdout(line)),
+                });
 
-        Ok(output)
-    }
+            result
+        });
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = cmd.try_wait()? {
+                break Ok(status);
+            }
+
+            if timeout.is_some_and(|timeout| started.elapsed() > timeout) {
+                cmd.kill()?;
+                cmd.wait()?;
+                break Err(Error::TimedOut);
+            }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+            thread::sleep(Duration::from_millis(10));
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        // NOTE: join the readers before returning, so that all output has been written to `output`.
+        let result = stdout_thread.join().map_err(|_| Error::BrokenPipe)?;
+        let panic_message = stderr_thread.join().map_err(|_| Error::BrokenPipe)?;
+        let status = status?;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        if status.success() {
+            Ok(result)
+        } else {
+            Err(panic_message.map_or(Error::Exited(status.code()), Error::Panicked))
+        }
+    }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    /// Build the solution bin for a given puzzle and return the path of its executable.
+    fn build_solution(
+        puzzle: PuzzleId,
+        is_release: bool,
+        output: &Output,
+    ) -> Result<PathBuf, Error> {
+        let bin_name = puzzle.to_string();
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--bin",
+            &bin_name,
+            "--message-format",
+            "json-render-diagnostics",
+        ];
 
-                timings.total_nanos += nanos;
-            });
+        if is_release {
+            args.push("--release");
+        }
 
-        timings
-    }
+        // NOTE: build the solutions with the memory stats of the runner, features are not passed on otherwise.
+        if cfg!(feature = "memory-stats") {
+            args.extend(["--features", "memory-stats"]);
+        }
+
+        let stderr = if output.is_captured() {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        };
+        let build = cargo_command().args(&args).stderr(stderr).output()?;
+
+        String::from_utf8_lossy(&build.stderr)
+            .lines()
+            .for_each(|line| output.write(OutputLine::Stderr(line.to_string())));
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+        if !build.status.success() {
+            return Err(Error::BuildFailed);
+        }
+
+        String::from_utf8_lossy(&build.stdout)
+            .lines()
+            .find_map(|line| parse_executable(line, &bin_name))
+            .ok_or(Error::BuildFailed)
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Parse the path of an executable from a `compiler-artifact` message of `cargo build --message-format json`.
+    fn parse_executable(line: &str, bin_name: &str) -> Option<PathBuf> {
+        let json = line.parse::<JsonValue>().ok()?;
+        let message = json.get::<HashMap<String, JsonValue>>()?;
+
+        let is_artifact = message
+            .get("reason")
+            .and_then(|v| v.get::<String>())
+            .is_some_and(|reason| reason == "compiler-artifact");
+
+        let is_bin = message
+            .get("target")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .and_then(|target| target.get("name"))
+            .and_then(|v| v.get::<String>())
+            .is_some_and(|name| name == bin_name);
+
+        if !(is_artifact && is_bin) {
+            return None;
+        }
+
+        message
+            .get("executable")
+            .and_then(|v| v.get::<String>())
+            .map(PathBuf::from)
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Parse a line of `--format json` output. Returns `None` for lines that are not records,
+    /// e.g. debug output of the solution itself.
+    pub fn parse_record(line: &str) -> Option<Record> {
+        let json = line.trim().parse::<JsonValue>().ok()?;
+        PartResult::try_from(&json)
+            .map(Record::Part)
+            .or_else(|_| ParseResult::try_from(&json).map(Record::Parse))
+            .ok()
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_executable, parse_record, Record};
+        use crate::{
+            puzzle,
+            template::{runner::SolutionResult, timings::Timing},
+        };
+        use std::time::Duration;
+
+        fn parse_output(lines: &[&str]) -> SolutionResult {
+            let mut result = SolutionResult::default();
+            for record in lines.iter().filter_map(|l| parse_record(l)) {
+                match record {
+                    Record::Parse(parse) => result.parse = Some(parse),
+                    Record::Part(part) => result.parts.push(part),
+                }
+            }
+            result
+        }
 
-        use crate::day;
+        #[test]
+        fn parses_records() {
+            let result = parse_output(&[
+                r#"{"part":1,"status":"solved","answer":"0","duration_nanos":74,"samples":1,"stats":null}"#,
+                r#"{"part":2,"status":"solved","answer":"10","duration_nanos":74130000,"samples":1,"stats":null}"#,
+                "",
+            ]);
+
+            let timing = Timing::from_results(puzzle!(2023, 1), &result);
+            assert_eq!(result.parts.len(), 2);
+            assert_eq!(result.parts[1].duration, Duration::from_nanos(74130000));
+            assert_eq!(timing.total_nanos, 74130074_f64);
+            assert!(timing.parse.is_none());
+            assert_eq!(timing.part_1.unwrap(), "74.0ns");
+            assert_eq!(timing.part_2.unwrap(), "74.1ms");
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+        fn parses_parse_records() {
+            let result = parse_output(&[
+                r#"{"step":"parse","duration_nanos":1000,"samples":1,"stats":null}"#,
+                r#"{"part":1,"status":"solved","answer":"0","duration_nanos":74,"samples":1,"stats":null}"#,
+            ]);
+
+            let timing = Timing::from_results(puzzle!(2023, 1), &result);
+            assert_eq!(result.parts.len(), 1);
+            assert_eq!(result.parse.unwrap().duration, Duration::from_nanos(1000));
+            assert_eq!(timing.total_nanos, 1074_f64);
+            assert_eq!(timing.parse.unwrap(), "1.0µs");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        fn parses_with_patterns_in_answer() {
+            let Some(Record::Part(result)) = parse_record(
+                r#"{"part":1,"status":"solved","answer":"@ @ @ ( ) ms (2s @ 5 samples)","duration_nanos":2000000000,"samples":5,"stats":null}"#,
+            ) else {
+                panic!("expected a part record");
+            };
+            assert_eq!(result.answer.unwrap(), "@ @ @ ( ) ms (2s @ 5 samples)");
+            assert_eq!(result.duration, Duration::from_secs(2));
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+            let result = parse_output(&[
+                r#"{"part":1,"status":"unsolved","answer":null,"duration_nanos":10,"samples":1,"stats":null}"#,
+                r#"{"part":2,"status":"unsolved","answer":null,"duration_nanos":10,"samples":1,"stats":null}"#,
+            ]);
+
+            let timing = Timing::from_results(puzzle!(2023, 1), &result);
+            assert_eq!(timing.total_nanos, 0_f64);
+            assert!(timing.part_1.is_none());
+            assert!(timing.part_2.is_none());
+        }
+
+        #[test]
+        fn parses_executables() {
+            let line = r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"2023-01"},"executable":"/target/release/2023-01","fresh":true}"#;
+            assert_eq!(
+                parse_executable(line, "2023-01"),
+                Some("/target/release/2023-01".into())
+            );
+            assert_eq!(parse_executable(line, "2023-02"), None);
+            assert_eq!(
+                parse_executable(r#"{"reason":"build-finished","success":true}"#, "2023-01"),
+                None
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn ignores_other_output() {
+            assert!(parse_record("Part 1: 42 (74.13ns @ 100 samples)").is_none());
+            assert!(parse_record(r#"{"foo": 1}"#).is_none());
+            assert!(parse_record("[1, 2]").is_none());
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run_parallel, Output, OutputLine};
+    use crate::{
+        template::{Day, PuzzleId},
+        year,
+    };
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn reports_parallel_results_in_order() {
+        let puzzles: Vec<_> = (1..=8)
+            .map(|day| PuzzleId::new(year!(2023), Day::new(day).unwrap()))
+            .collect();
+        let mut reported = vec![];
+
+        run_parallel(
+            &puzzles,
+            3,
+            |puzzle| {
+                let output = Output::captured();
+                output.write(OutputLine::Stdout(format!("started {puzzle}")));
+                // finish later days first.
+                thread::sleep(Duration::from_millis(
+                    5 * u64::from(9 - puzzle.day.into_inner()),
+                ));
+                output.write(OutputLine::Stderr(format!("finished {puzzle}")));
+                (puzzle.day, output.into_lines())
+            },
+            |puzzle, (day, output): (Day, Vec<OutputLine>)| {
+                assert_eq!(puzzle.day, day);
+                assert_eq!(
+                    output,
+                    [
+                        OutputLine::Stdout(format!("started {puzzle}")),
+                        OutputLine::Stderr(format!("finished {puzzle}")),
+                    ]
+                );
+                reported.push(puzzle);
+            },
+        );
+
+        assert_eq!(reported, puzzles);
+    }
+
+    #[test]
+    fn prints_uncaptured_output() {
+        let output = Output::default();
+        output.write(OutputLine::Stdout("printed".into()));
+        assert!(output.into_lines().is_empty());
     }
 }
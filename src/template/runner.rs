@@ -1,65 +1,643 @@
 /// Encapsulates code that interacts with solution functions.
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, IsTerminal, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use tinyjson::JsonValue;
+
+use crate::template::answers::{self, AnswerCheck, Answers};
+use crate::template::aoc_client::{AocClient, AocClientError, SubmissionOutcome};
+use crate::template::commands::download;
+use crate::template::config::config;
+use crate::template::cpu_profile;
+use crate::template::examples::example_answers;
+use crate::template::integrity;
+use crate::template::memory::{self, MemoryStats};
+use crate::template::submissions::{self, Outcome, SubmissionLog};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+/// Outcome of running a single solution part.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Error message of a part that returned `Err`.
+    pub error: Option<String>,
+    pub duration: Duration,
+    pub stats: Option<BenchStats>,
+    /// Heap usage of the first run, if the `memory-stats` feature is enabled.
+    pub memory: Option<MemoryStats>,
+}
+
+/// Return value of a solution part, either an `Option` or a `Result` of a displayable answer.
+pub trait PartOutput {
+    /// Returns the answer, `None` for an unsolved part, or the error message of a failed part.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// Outcome of running the parse step of a solution.
+#[derive(Clone, Debug)]
+pub struct ParseResult {
+    pub duration: Duration,
+    pub stats: Option<BenchStats>,
+}
+
+/// Outcome of running a solution: the optional parse step and every part.
+#[derive(Clone, Debug, Default)]
+pub struct SolutionResult {
+    pub parse: Option<ParseResult>,
+    pub parts: Vec<PartResult>,
+}
+
+/// Summary statistics of a benchmark run. Outliers are excluded from `mean`, `p95` and `stddev`,
+/// `min` and `max` show the extremes of all samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub outliers: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl BenchStats {
+    /// Computes statistics from raw sample durations.
+    /// Samples outside of the Tukey fences (1.5 × IQR beyond the quartiles) are rejected as outliers.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 25.0);
+        let q3 = percentile(&sorted, 75.0);
+        let fence = (q3 - q1) * 3 / 2;
+        let (lower, upper) = (q1.saturating_sub(fence), q3 + fence);
+
+        let kept: Vec<u128> = sorted
+            .iter()
+            .copied()
+            .filter(|x| (lower..=upper).contains(x))
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let (mean, stddev) = {
+            let n = kept.len() as f64;
+            let mean = kept.iter().sum::<u128>() as f64 / n;
+            let variance = if kept.len() > 1 {
+                kept.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / (n - 1.0)
+            } else {
+                0.0
+            };
+            (mean, variance.sqrt())
+        };
+
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2
+        } else {
+            sorted[sorted.len() / 2]
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            samples: sorted.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+            mean: Duration::from_nanos(mean as u64),
+            median: nanos_to_duration(median),
+            min: nanos_to_duration(sorted[0]),
+            max: nanos_to_duration(sorted[sorted.len() - 1]),
+            p95: nanos_to_duration(percentile(&kept, 95.0)),
+            stddev: Duration::from_nanos(stddev as u64),
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn percentile(sorted: &[u128], p: f64) -> u128 {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn nanos_to_duration(nanos: u128) -> Duration {
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
+
+/// Output format of the solution binaries, selected with `--format <text|json>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output, with intermediate results and progress.
+    Text,
+    /// One JSON record per part and line, see [`PartResult`].
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown output format `{s}`, expected `text` or `json`."
+            )),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => f.write_str("text"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// Parse the `--format` argument passed to the solution binary.
+fn output_format() -> OutputFormat {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--format") else {
+        return OutputFormat::Text;
+    };
+
+    match args.get(index + 1).map(|x| x.parse()) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Unexpected command-line input. Format: --format <text|json>");
+            process::exit(1);
+        }
+    }
+}
+
+/// Value of a command-line flag passed to the solution binary, e.g. `--part 1`.
+/// Exits if the flag is passed without a value.
+fn flag_value(flag: &str, format: &str) -> Option<String> {
+    let mut args = env::args().skip_while(|x| x != flag);
+    args.next()?;
+
+    match args.next() {
+        Some(value) => Some(value),
+        None => {
+            eprintln!("Unexpected command-line input. Format: {flag} {format}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse the `--part` argument passed to the solution binary. Returns `None` if all parts should run.
+/// Exits if the part is not one of the `available` parts of the solution.
+pub fn selected_part(available: &[u8]) -> Option<u8> {
+    part_flag("--part", available)
+}
+
+/// Parse the `--submit` argument passed to the solution binary. Returns `None` if nothing should be
+/// submitted. Exits if the part is not one of the `available` parts, or if `--part` selected another part.
+pub fn submitted_part(available: &[u8], selected: Option<u8>) -> Option<u8> {
+    let part = part_flag("--submit", available)?;
+
+    if let Some(selected) = selected.filter(|selected| *selected != part) {
+        eprintln!("Can not submit part {part}, only part {selected} runs.");
+        process::exit(1);
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    Some(part)
+}
+
+/// Parse the part passed with `flag`. Exits if it is not one of the `available` parts of the solution.
+fn part_flag(flag: &str, available: &[u8]) -> Option<u8> {
+    let value = flag_value(flag, "<1|2>")?;
+
+    match value.parse::<u8>() {
+        Ok(part) if available.contains(&part) => Some(part),
+        Ok(part @ 1..=2) => {
+            eprintln!("This solution does not have a part {part}.");
+            process::exit(1);
+        }
+        _ => {
+            eprintln!("Unexpected part `{value}`, expected 1 or 2.");
+            process::exit(1);
+        }
+    }
+}
+
+/// Where the solution binary reads its input from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum InputSource {
+    /// The puzzle input in `data/{year}/inputs`.
+    Puzzle,
+    /// An example in `data/{year}/examples`, `NN.txt` or `NN-k.txt` for `Some(k)`.
+    Example(Option<u8>),
+    /// The path passed with `--input`, `-` for stdin.
+    Custom(String),
+}
+
+/// Parse the `--example[=k]` and `--input <path|->` arguments passed to the solution binary.
+fn input_source() -> InputSource {
+    if let Some(path) = flag_value("--input", "<path|->") {
+        return InputSource::Custom(path);
+    }
+
+    let Some(arg) = env::args().find(|x| x == "--example" || x.starts_with("--example=")) else {
+        return InputSource::Puzzle;
+    };
+
+    match arg.strip_prefix("--example=") {
+        None => InputSource::Example(None),
+        Some(value) => match value.parse() {
+            Ok(k) => InputSource::Example(Some(k)),
+            Err(_) => {
+                eprintln!("Unexpected example `{value}`. Format: --example[=k]");
+                process::exit(1);
+            }
+        },
+    }
+}
+
+impl InputSource {
+    /// Path of the input file, `None` for stdin.
+    fn path(&self, puzzle: PuzzleId) -> Option<PathBuf> {
+        match self {
+            InputSource::Puzzle => Some(puzzle.data_path("inputs", "txt")),
+            InputSource::Example(None) => Some(puzzle.data_path("examples", "txt")),
+            InputSource::Example(Some(k)) => Some(
+                puzzle
+                    .data_dir("examples")
+                    .join(format!("{}-{k}.txt", puzzle.day)),
+            ),
+            InputSource::Custom(path) if path == "-" => None,
+            InputSource::Custom(path) => Some(PathBuf::from(path)),
+        }
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> io::Result<String> {
+        match self.path(puzzle) {
+            Some(path) => fs::read_to_string(path),
+            None => io::read_to_string(io::stdin()),
+        }
+    }
+}
+
+/// Read the puzzle input. Reads the path passed with `--input <path>`, stdin for `--input -`,
+/// an example for `--example[=k]`, and `data/{year}/inputs` otherwise.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let source = input_source();
+    if source == InputSource::Puzzle {
+        return read_puzzle_input(puzzle);
+    }
+
+    match source.read(puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            let name = source
+                .path(puzzle)
+                .map_or("stdin".into(), |path| path.display().to_string());
+            eprintln!("Failed to read input \"{name}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Read and validate the puzzle input. If it is missing or invalid, offers to download it when run
+/// interactively, and exits otherwise.
+fn read_puzzle_input(puzzle: PuzzleId) -> String {
+    let error = match integrity::read_verified(puzzle) {
+        Ok(input) => return input,
+        Err(e) => e,
+    };
+
+    if output_format() == OutputFormat::Text && io::stdin().is_terminal() {
+        eprint!("The input of {puzzle} {error}. Download it now? [y/N] ");
+        let _ = io::stderr().flush();
+
+        let mut answer = String::new();
+        let _ = io::stdin().read_line(&mut answer);
+
+        if answer.trim().eq_ignore_ascii_case("y") {
+            download::handle(puzzle);
+            println!();
+
+            match integrity::read_verified(puzzle) {
+                Ok(input) => return input,
+                Err(e) => eprintln!("The input of {puzzle} {e}."),
+            }
+        }
+    } else {
+        eprintln!("The input of {puzzle} {error}.");
+    }
+
+    eprintln!(
+        "Run `cargo download {} --year {}` to download it.",
+        puzzle.day.into_inner(),
+        puzzle.year
+    );
+    process::exit(1);
+}
+
+/// Expected answers for the input, `None` for custom inputs. The answers of an example are taken
+/// from the stored puzzle description.
+fn expected_answers(puzzle: PuzzleId, source: &InputSource) -> Option<Answers> {
+    match source {
+        InputSource::Puzzle => Some(Answers::read(puzzle)),
+        InputSource::Example(_) => {
+            let description = fs::read_to_string(puzzle.data_path("puzzles", "md")).ok()?;
+            let input = source.read(puzzle).ok()?;
+            Some(example_answers(&description, &input))
+        }
+        InputSource::Custom(_) => None,
+    }
+}
+
+/// Run a part of the solution and print its result. The answer is submitted if `submit` is set.
+pub fn run_part<I: Clone, T: PartOutput>(
+    func: impl Fn(I) -> T,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    submit: bool,
+) {
     let part_str = format!("Part {part}");
+    let format = output_format();
+    let is_text = format == OutputFormat::Text;
+
+    let profile_input = env::args()
+        .any(|x| x == "--profile-cpu")
+        .then(|| input.clone());
+
+    let (result, duration, stats, memory) = run_timed(&func, input, is_text, |result| {
+        if is_text {
+            print_result(&result.to_answer(), &part_str, "");
+        }
+    });
+
+    let result = result.to_answer();
+    let answer = result.clone().ok().flatten();
+
+    let source = input_source();
+
+    match format {
+        OutputFormat::Text => {
+            let check = expected_answers(puzzle, &source)
+                .map_or(AnswerCheck::Unknown, |a| a.check(part, answer.as_deref()));
+            print_result(
+                &result,
+                &part_str,
+                &format!(
+                    "{}{}{}",
+                    format_duration(&duration, stats.as_ref()),
+                    format_memory(memory.as_ref()),
+                    format_check(&check)
+                ),
+            );
+        }
+        OutputFormat::Json => {
+            let record = PartResult {
+                part,
+                answer: answer.clone(),
+                error: result.err(),
+                duration,
+                stats,
+                memory,
+            };
+            println!("{}", JsonValue::from(&record).stringify().unwrap());
+        }
+    }
+
+    if let Some(input) = profile_input {
+        match cpu_profile::profile(&func, input, puzzle, part) {
+            Ok((svg, folded)) => println!(
+                "{ANSI_ITALIC}Wrote CPU profile of part {part} to \"{}\" and \"{}\".{ANSI_RESET}",
+                svg.display(),
+                folded.display()
+            ),
+            Err(e) => eprintln!("Failed to profile part {part}: {e}"),
+        }
+    }
+
+    if let Some(answer) = answer {
+        // NOTE: answers for other inputs say nothing about the answers of the puzzle.
+        if source != InputSource::Puzzle {
+            if submit || env::args().any(|x| x == "--record") {
+                eprintln!("Not recording or submitting the answer of part {part}, it was not computed for the puzzle input.");
+            }
+            return;
+        }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+        if env::args().any(|x| x == "--record") {
+            answers::record(puzzle, part, &answer);
+        }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        if !submit {
+            return;
+        }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        // NOTE: with `--format json`, stdout is reserved for the json records.
+        match submit_result(&answer, puzzle, part, is_text) {
+            Some(Ok(outcome)) => {
+                if is_text {
+                    println!("{outcome}");
+                } else {
+                    eprintln!("{outcome}");
+                }
+                if outcome == SubmissionOutcome::Correct {
+                    answers::record(puzzle, part, &answer);
+                }
+            }
+            Some(Err(e)) => eprintln!("Failed to submit result: {e}"),
+            None => {}
+        }
     }
 }
 
+/// Run the parse step of a solution and print its duration. Returns the parsed input.
+pub fn run_parse<'a, P>(func: impl Fn(&'a str) -> P, input: &'a str) -> (P, ParseResult) {
+    let format = output_format();
+
+    let (parsed, duration, stats, _) = run_timed(func, input, false, |_| {});
+    let result = ParseResult { duration, stats };
+
+    match format {
+        OutputFormat::Text => print_parse_result(&result),
+        OutputFormat::Json => println!("{}", JsonValue::from(&result).stringify().unwrap()),
+    }
+
+    (parsed, result)
+}
+
+/// Run the parse step of a solution without printing anything. Used by the in-process runner.
+pub fn solve_parse<'a, P>(
+    func: impl Fn(&'a str) -> P,
+    input: &'a str,
+    is_timed: bool,
+) -> (P, ParseResult) {
+    let timer = Instant::now();
+    let parsed = func(input);
+    let base_time = timer.elapsed();
+
+    let stats = is_timed.then(|| bench(func, input, &base_time, false));
+
+    let result = ParseResult {
+        duration: stats.as_ref().map_or(base_time, |s| s.median),
+        stats,
+    };
+
+    (parsed, result)
+}
+
+/// Run a solution part without printing or submitting anything. Used by the in-process runner.
+pub fn solve_part<I: Clone, T: PartOutput>(
+    func: impl Fn(I) -> T,
+    input: I,
+    part: u8,
+    is_timed: bool,
+) -> PartResult {
+    let timer = Instant::now();
+    let (result, memory) = memory::measure(|| func(input.clone()).to_answer());
+    let base_time = timer.elapsed();
+
+    let stats = is_timed.then(|| bench(func, input, &base_time, false));
+
+    let (answer, error) = match result {
+        Ok(answer) => (answer, None),
+        Err(e) => (None, Some(e)),
+    };
+
+    PartResult {
+        part,
+        answer,
+        error,
+        duration: stats.as_ref().map_or(base_time, |s| s.median),
+        stats,
+        memory,
+    }
+}
+
+impl PartResult {
+    /// Status of the part as reported in JSON records.
+    pub fn status(&self) -> &'static str {
+        if self.error.is_some() {
+            "failed"
+        } else if self.answer.is_some() {
+            "solved"
+        } else {
+            "unsolved"
+        }
+    }
+
+    /// The answer of the part, or its error message if it failed.
+    pub fn to_answer(&self) -> Result<Option<String>, String> {
+        match &self.error {
+            Some(e) => Err(e.clone()),
+            None => Ok(self.answer.clone()),
+        }
+    }
+}
+
+/// Print the duration of a parse step in the same format as the solution binaries.
+pub fn print_parse_result(result: &ParseResult) {
+    println!(
+        "Parse:{}",
+        format_duration(&result.duration, result.stats.as_ref())
+    );
+}
+
+/// Print the final result line of a part in the same format as the solution binaries.
+pub fn print_part_result(result: &PartResult, check: &AnswerCheck) {
+    print_result(
+        &result.to_answer(),
+        &format!("Part {}", result.part),
+        &format!(
+            "{}{}{}",
+            format_duration(&result.duration, result.stats.as_ref()),
+            format_memory(result.memory.as_ref()),
+            format_check(check)
+        ),
+    );
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// The heap usage is collected from the first run.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    show_progress: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<BenchStats>, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        memory::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if std::env::args().any(|x| x == "--time") {
+        let stats = bench(func, input, &base_time, show_progress);
+        (result, stats.median, Some(stats), memory)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None, memory)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    show_progress: bool,
+) -> BenchStats {
+    if show_progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let settings = &config().bench;
+    let bench_iterations = (settings.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(settings.min_samples.into(), settings.max_samples.into());
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // warm up caches and branch predictors before measuring.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,34 +649,32 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
+}
+
+fn format_duration(duration: &Duration, stats: Option<&BenchStats>) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(stats) => format!(
+            " ({duration:.1?} @ {} samples) [mean {:.1?}, min {:.1?}, max {:.1?}, p95 {:.1?}, stddev {:.1?}, {} outliers]",
+            stats.samples, stats.mean, stats.min, stats.max, stats.p95, stats.stddev, stats.outliers
+        ),
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_memory(memory: Option<&MemoryStats>) -> String {
+    memory.map_or_else(String::new, |memory| format!(" [{memory}]"))
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
+fn format_check(check: &AnswerCheck) -> String {
+    format!(" {check}")
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Result<Option<String>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,7 +694,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,44 +702,351 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(e) => {
+            let str = format!("{part}: ✖ {ANSI_BOLD}error:{ANSI_RESET} {e}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}{duration_str}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Try to submit one part of the solution if:
+///  1. a session cookie is configured.
+///  2. the submission log does not already rule out the answer.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    is_text: bool,
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
+    let answer = result.to_string();
 
-    if !args.contains(&"--submit".into()) {
+    if let Err(rejection) = SubmissionLog::read(puzzle).check(part, &answer) {
+        eprintln!("Refusing to submit `{answer}`: {rejection}");
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up the aoc client: {e}");
+            process::exit(1);
+        }
+    };
+
+    if is_text {
+        println!("Submitting result...");
+    } else {
+        eprintln!("Submitting result...");
     }
+    let outcome = client.submit(puzzle, part, &answer);
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    if let Some(logged) = outcome.as_ref().ok().and_then(Outcome::from_submission) {
+        submissions::record(puzzle, part, &answer, logged);
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    Some(outcome)
+}
 
-    if part_submit != part {
-        return None;
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("status".into(), JsonValue::String(value.status().into()));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.stats.as_ref().map_or(1, |s| s.samples) as f64),
+        );
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
     }
+}
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part record to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        // NOTE: records of older solution binaries do not have an error.
+        let error = match json.get("error") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.error to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let duration = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| Duration::from_nanos(*v as u64))
+            .ok_or("Expected record.duration_nanos to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        // NOTE: records of solution binaries built without `memory-stats` have no memory stats.
+        let memory = match json.get("memory") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(MemoryStats::try_from(v)?),
+        };
+
+        Ok(PartResult {
+            part,
+            answer: answer.cloned(),
+            error,
+            duration,
+            stats,
+            memory,
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&ParseResult> for JsonValue {
+    fn from(value: &ParseResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("step".into(), JsonValue::String("parse".into()));
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.stats.as_ref().map_or(1, |s| s.samples) as f64),
+        );
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for ParseResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected parse record to be a JSON object.")?;
+
+        json.get("step")
+            .and_then(|v| v.get::<String>())
+            .filter(|step| *step == "parse")
+            .ok_or("Expected record.step to be `parse`.")?;
+
+        let duration = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| Duration::from_nanos(*v as u64))
+            .ok_or("Expected record.duration_nanos to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        Ok(ParseResult { duration, stats })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchStats, MemoryStats, ParseResult, PartOutput, PartResult};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_part_records() {
+        let record = PartResult {
+            part: 1,
+            answer: Some("@ @ ( ) ms\nline".into()),
+            error: None,
+            duration: Duration::from_nanos(74),
+            stats: Some(BenchStats::from_samples(&nanos(&[74, 75, 73]))),
+            memory: Some(MemoryStats {
+                peak_bytes: 1024,
+                total_bytes: 4096,
+                allocations: 3,
+            }),
+        };
+
+        let json = JsonValue::from(&record).stringify().unwrap();
+        let parsed = PartResult::try_from(&json.parse::<JsonValue>().unwrap()).unwrap();
+
+        assert_eq!(parsed.part, 1);
+        assert_eq!(parsed.answer, record.answer);
+        assert_eq!(parsed.duration, record.duration);
+        assert_eq!(parsed.stats, record.stats);
+        assert_eq!(parsed.memory, record.memory);
+        assert_eq!(parsed.status(), "solved");
+    }
+
+    #[test]
+    fn roundtrips_parse_records() {
+        let record = ParseResult {
+            duration: Duration::from_nanos(1200),
+            stats: None,
+        };
+
+        let json = JsonValue::from(&record).stringify().unwrap();
+        let value = json.parse::<JsonValue>().unwrap();
+        let parsed = ParseResult::try_from(&value).unwrap();
+
+        assert_eq!(parsed.duration, record.duration);
+        assert!(parsed.stats.is_none());
+        assert!(PartResult::try_from(&value).is_err());
+    }
+
+    #[test]
+    fn parses_unsolved_part_records() {
+        let json = r#"{ "part": 2, "status": "unsolved", "answer": null, "duration_nanos": 10, "samples": 1, "stats": null }"#;
+        let parsed = PartResult::try_from(&json.parse::<JsonValue>().unwrap()).unwrap();
+
+        assert_eq!(parsed.part, 2);
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.status(), "unsolved");
+    }
+
+    #[test]
+    fn roundtrips_failed_part_records() {
+        let record = PartResult {
+            part: 1,
+            answer: None,
+            error: Some("invalid line `#.?`".into()),
+            duration: Duration::from_nanos(10),
+            stats: None,
+            memory: None,
+        };
+
+        let json = JsonValue::from(&record).stringify().unwrap();
+        let parsed = PartResult::try_from(&json.parse::<JsonValue>().unwrap()).unwrap();
+
+        assert_eq!(parsed.error, record.error);
+        assert_eq!(parsed.status(), "failed");
+        assert_eq!(parsed.to_answer(), Err("invalid line `#.?`".into()));
+    }
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(42).to_answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.to_answer(), Ok(None));
+        assert_eq!(Ok::<_, String>(42).to_answer(), Ok(Some("42".into())));
+        assert_eq!(Err::<u32, _>("oops").to_answer(), Err("oops".into()));
+    }
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_bench_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[40, 10, 30, 20]));
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.mean, Duration::from_nanos(25));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(40));
+        assert_eq!(stats.p95, Duration::from_nanos(40));
+        assert_eq!(stats.stddev, Duration::from_nanos(12));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 11, 5000]));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.p95, Duration::from_nanos(12));
+        assert_eq!(stats.mean, Duration::from_nanos(10));
+    }
+
+    #[test]
+    fn keeps_outliers_in_min_and_max() {
+        let stats = BenchStats::from_samples(&nanos(&[2, 100, 101, 100, 102, 101, 100, 5000]));
+        assert_eq!(stats.outliers, 2);
+        assert_eq!(stats.min, Duration::from_nanos(2));
+        assert_eq!(stats.max, Duration::from_nanos(5000));
+        assert_eq!(stats.p95, Duration::from_nanos(102));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
 }
@@ -0,0 +1,360 @@
+/// Keeps a log of submitted answers, so that answers known to be wrong are not submitted twice.
+use std::{collections::HashMap, fmt::Display, fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::SubmissionOutcome, PuzzleId};
+
+/// Outcome of a logged submission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+}
+
+/// A single submitted answer and its outcome.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// The submissions of both parts of a puzzle.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub part_1: Vec<Submission>,
+    pub part_2: Vec<Submission>,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadySolved(String),
+    KnownWrong,
+    TooHigh(String),
+    TooLow(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadySolved(answer) => {
+                write!(f, "this part has already been solved with `{answer}`.")
+            }
+            Rejection::KnownWrong => write!(f, "this answer has already been rejected."),
+            Rejection::TooHigh(bound) => {
+                write!(
+                    f,
+                    "answers of `{bound}` or higher are known to be too high."
+                )
+            }
+            Rejection::TooLow(bound) => {
+                write!(f, "answers of `{bound}` or lower are known to be too low.")
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path_for_submissions(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path("submissions", "json")
+}
+
+impl Outcome {
+    /// Maps the outcome reported by the website to a loggable outcome.
+    /// Returns `None` for outcomes that say nothing about the answer itself.
+    pub fn from_submission(outcome: &SubmissionOutcome) -> Option<Self> {
+        match outcome {
+            SubmissionOutcome::Correct => Some(Outcome::Correct),
+            SubmissionOutcome::Incorrect => Some(Outcome::Incorrect),
+            SubmissionOutcome::TooHigh => Some(Outcome::TooHigh),
+            SubmissionOutcome::TooLow => Some(Outcome::TooLow),
+            SubmissionOutcome::RateLimited(_) => Some(Outcome::RateLimited),
+            SubmissionOutcome::WrongLevel | SubmissionOutcome::Unknown(_) => None,
+        }
+    }
+}
+
+impl SubmissionLog {
+    /// Rehydrate the log of a puzzle from its JSON file. If not present, returns an empty log.
+    pub fn read(puzzle: PuzzleId) -> Self {
+        let s = fs::read_to_string(get_path_for_submissions(puzzle))
+            .map_err(|x| x.to_string())
+            .and_then(SubmissionLog::try_from);
+
+        match s {
+            Ok(log) => log,
+            Err(e) => {
+                if get_path_for_submissions(puzzle).exists() {
+                    eprintln!("{e}");
+                }
+                SubmissionLog::default()
+            }
+        }
+    }
+
+    /// Dehydrate the log of a puzzle to its JSON file.
+    pub fn store(&self, puzzle: PuzzleId) -> Result<(), io::Error> {
+        let path = get_path_for_submissions(puzzle);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> &[Submission] {
+        match part {
+            1 => &self.part_1,
+            2 => &self.part_2,
+            _ => &[],
+        }
+    }
+
+    pub fn push(&mut self, part: u8, submission: Submission) {
+        match part {
+            1 => self.part_1.push(submission),
+            2 => self.part_2.push(submission),
+            _ => {}
+        }
+    }
+
+    /// Check whether submitting `answer` for `part` could be correct, based on earlier submissions.
+    /// Range checks only apply if both the answer and the logged bound are integers.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Rejection> {
+        let submissions = self.get(part);
+
+        if let Some(correct) = submissions.iter().find(|s| s.outcome == Outcome::Correct) {
+            return Err(Rejection::AlreadySolved(correct.answer.clone()));
+        }
+
+        let is_known_wrong = submissions.iter().any(|s| {
+            s.answer == answer && !matches!(s.outcome, Outcome::RateLimited | Outcome::Correct)
+        });
+
+        if is_known_wrong {
+            return Err(Rejection::KnownWrong);
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |outcome: Outcome| {
+            submissions
+                .iter()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(upper) = bound(Outcome::TooHigh).min() {
+            if value >= upper {
+                return Err(Rejection::TooHigh(upper.to_string()));
+            }
+        }
+
+        if let Some(lower) = bound(Outcome::TooLow).max() {
+            if value <= lower {
+                return Err(Rejection::TooLow(lower.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Append a submission for a part of a puzzle to its log.
+pub fn record(puzzle: PuzzleId, part: u8, answer: &str, outcome: Outcome) {
+    let mut log = SubmissionLog::read(puzzle);
+
+    log.push(
+        part,
+        Submission {
+            answer: answer.into(),
+            outcome,
+        },
+    );
+
+    if let Err(e) = log.store(puzzle) {
+        eprintln!("Failed to log submission: {e}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => f.write_str("correct"),
+            Outcome::Incorrect => f.write_str("incorrect"),
+            Outcome::TooHigh => f.write_str("too_high"),
+            Outcome::TooLow => f.write_str("too_low"),
+            Outcome::RateLimited => f.write_str("rate_limited"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "incorrect" => Ok(Outcome::Incorrect),
+            "too_high" => Ok(Outcome::TooHigh),
+            "too_low" => Ok(Outcome::TooLow),
+            "rate_limited" => Ok(Outcome::RateLimited),
+            _ => Err(format!("unknown submission outcome `{s}`.")),
+        }
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.to_string()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.outcome to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            answer: answer.clone(),
+            outcome,
+        })
+    }
+}
+
+impl From<SubmissionLog> for JsonValue {
+    fn from(value: SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "part_1".into(),
+            JsonValue::Array(value.part_1.iter().map(JsonValue::from).collect()),
+        );
+        map.insert(
+            "part_2".into(),
+            JsonValue::Array(value.part_2.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SubmissionLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let parse_part = |key: &str| -> Result<Vec<Submission>, String> {
+            json.get(key)
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .ok_or(format!("expected `json.{key}` to be an array."))?
+                .iter()
+                .map(Submission::try_from)
+                .collect()
+        };
+
+        Ok(SubmissionLog {
+            part_1: parse_part("part_1")?,
+            part_2: parse_part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Outcome, Rejection, Submission, SubmissionLog};
+    use tinyjson::JsonValue;
+
+    fn submission(answer: &str, outcome: Outcome) -> Submission {
+        Submission {
+            answer: answer.into(),
+            outcome,
+        }
+    }
+
+    fn get_mock_log() -> SubmissionLog {
+        SubmissionLog {
+            part_1: vec![
+                submission("100", Outcome::TooHigh),
+                submission("10", Outcome::TooLow),
+                submission("50", Outcome::Incorrect),
+                submission("60", Outcome::RateLimited),
+            ],
+            part_2: vec![submission("abc", Outcome::Correct)],
+        }
+    }
+
+    #[test]
+    fn accepts_unknown_answers() {
+        let log = get_mock_log();
+        assert_eq!(log.check(1, "42"), Ok(()));
+        assert_eq!(log.check(1, "60"), Ok(()));
+        assert_eq!(log.check(1, "foo"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let log = get_mock_log();
+        assert_eq!(log.check(1, "50"), Err(Rejection::KnownWrong));
+    }
+
+    #[test]
+    fn rejects_answers_out_of_range() {
+        let log = get_mock_log();
+        assert_eq!(log.check(1, "100"), Err(Rejection::KnownWrong));
+        assert_eq!(log.check(1, "120"), Err(Rejection::TooHigh("100".into())));
+        assert_eq!(log.check(1, "-5"), Err(Rejection::TooLow("10".into())));
+    }
+
+    #[test]
+    fn rejects_solved_parts() {
+        let log = get_mock_log();
+        assert_eq!(
+            log.check(2, "def"),
+            Err(Rejection::AlreadySolved("abc".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let log = get_mock_log();
+        let json = JsonValue::from(log.clone()).stringify().unwrap();
+        assert_eq!(SubmissionLog::try_from(json).unwrap(), log);
+    }
+}
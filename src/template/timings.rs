@@ -1,20 +1,109 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::config::config;
+use crate::template::memory::MemoryStats;
+use crate::template::runner::{BenchStats, SolutionResult};
+use crate::template::{Day, PuzzleId, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
-/// Represents benchmark times for a single day.
+/// Represents benchmark times for a single puzzle.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
+    pub puzzle: PuzzleId,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
     pub total_nanos: f64,
 }
 
-/// Represents benchmark times for a set of days.
+impl Timing {
+    /// Collect the timings of a puzzle from the results of its parse step and parts.
+    /// Unsolved parts are skipped.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_results(puzzle: PuzzleId, result: &SolutionResult) -> Self {
+        let mut timing = Timing {
+            puzzle,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            total_nanos: 0_f64,
+        };
+
+        if let Some(parse) = &result.parse {
+            timing.parse = Some(format!("{:.1?}", parse.duration));
+            timing.parse_stats.clone_from(&parse.stats);
+            timing.total_nanos += parse.duration.as_nanos() as f64;
+        }
+
+        for result in result.parts.iter().filter(|r| r.answer.is_some()) {
+            let duration_str = Some(format!("{:.1?}", result.duration));
+
+            match result.part {
+                1 => {
+                    timing.part_1 = duration_str;
+                    timing.part_1_stats.clone_from(&result.stats);
+                    timing.part_1_memory = result.memory;
+                }
+                2 => {
+                    timing.part_2 = duration_str;
+                    timing.part_2_stats.clone_from(&result.stats);
+                    timing.part_2_memory = result.memory;
+                }
+                _ => continue,
+            }
+
+            timing.total_nanos += result.duration.as_nanos() as f64;
+        }
+
+        timing
+    }
+
+    /// Duration of the parse step, the median if the step was benched.
+    pub fn parse_duration(&self) -> Option<Duration> {
+        step_duration(self.parse.as_deref(), self.parse_stats.as_ref())
+    }
+
+    /// Duration of a solved part, the median if the part was benched.
+    pub fn part_duration(&self, part: u8) -> Option<Duration> {
+        match part {
+            1 => step_duration(self.part_1.as_deref(), self.part_1_stats.as_ref()),
+            2 => step_duration(self.part_2.as_deref(), self.part_2_stats.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Timings stored without stats only have the formatted duration, e.g. `1.2ms`.
+fn step_duration(formatted: Option<&str>, stats: Option<&BenchStats>) -> Option<Duration> {
+    let formatted = formatted?;
+    stats
+        .map(|s| s.median)
+        .or_else(|| parse_formatted_duration(formatted))
+}
+
+/// Parse a duration formatted with `{:?}`, e.g. `1.2ms` or `310.0ns`.
+fn parse_formatted_duration(s: &str) -> Option<Duration> {
+    let (value, factor) = [("ns", 1e-9), ("µs", 1e-6), ("ms", 1e-3), ("s", 1.0)]
+        .iter()
+        .find_map(|(unit, factor)| Some((s.strip_suffix(unit)?, factor)))?;
+
+    let value = value.parse::<f64>().ok()?;
+    Duration::try_from_secs_f64(value * factor).ok()
+}
+
+/// Represents benchmark times for a set of puzzles.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
@@ -25,13 +114,13 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(config().data_file(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+        let s = fs::read_to_string(config().data_file(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -53,12 +142,12 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.puzzle == timing.puzzle) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.puzzle);
         Timings { data }
     }
 
@@ -67,10 +156,10 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.puzzle == puzzle && t.part_1.is_some() && t.part_2.is_some())
     }
 }
 
@@ -118,9 +207,24 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year.to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -140,6 +244,40 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse_stats".into(),
+            value
+                .parse_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        for (key, memory) in [
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            map.insert(
+                key.into(),
+                memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -158,6 +296,17 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // NOTE: timings stored before multi-year support have no year, they belong to the configured year.
+        let year = match json.get("year") {
+            None => {
+                Year::configured().ok_or("Expected timing.year to be set, or a configured year.")?
+            }
+            Some(v) => v
+                .get::<String>()
+                .and_then(|year| Year::from_str(year).ok())
+                .ok_or("Expected timing.year to be a Year struct.")?,
+        };
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -173,10 +322,53 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: parse timings were added later, timings stored by older versions do not have them.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let parse_stats = match json.get("parse_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        // NOTE: stats were added later, timings stored by older versions do not have them.
+        let part_1_stats = match json.get("part_1_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        // NOTE: memory stats are only collected with the `memory-stats` feature.
+        let memory = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => MemoryStats::try_from(v).map(Some),
+        };
+
         Ok(Timing {
-            day,
+            puzzle: PuzzleId::new(year, day),
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            part_1_memory: memory("part_1_memory")?,
+            part_2_memory: memory("part_2_memory")?,
             total_nanos,
         })
     }
@@ -184,9 +376,64 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+#[allow(clippy::cast_precision_loss)]
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        for (key, duration) in [
+            ("mean_nanos", value.mean),
+            ("median_nanos", value.median),
+            ("min_nanos", value.min),
+            ("max_nanos", value.max),
+            ("p95_nanos", value.p95),
+            ("stddev_nanos", value.stddev),
+        ] {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+            mean: duration("mean_nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            p95: duration("p95_nanos")?,
+            stddev: duration("stddev_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::puzzle;
 
     use super::{Timing, Timings};
 
@@ -194,21 +441,39 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2023, 2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    puzzle: puzzle!(2023, 4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -216,20 +481,34 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{puzzle, template::timings::Timings};
+        use std::time::Duration;
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "year": "2023", "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.puzzle, puzzle!(2023, 1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "year": "2023", "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 100, "outliers": 2, "mean_nanos": 1100000, "median_nanos": 1000000, "min_nanos": 900000, "max_nanos": 1500000, "p95_nanos": 1400000, "stddev_nanos": 50000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 100);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(stats.median, Duration::from_millis(1));
+            assert_eq!(stats.p95, Duration::from_micros(1400));
+            assert!(timing.part_2_stats.is_none());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -254,9 +533,47 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
-        use std::collections::HashMap;
+        use crate::template::{memory::MemoryStats, runner::BenchStats, timings::Timings};
+        use std::{collections::HashMap, time::Duration};
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_stats() {
+            let mut timings = get_mock_timings();
+            let stats = BenchStats {
+                samples: 10,
+                outliers: 1,
+                mean: Duration::from_nanos(120),
+                median: Duration::from_nanos(100),
+                min: Duration::from_nanos(90),
+                max: Duration::from_nanos(200),
+                p95: Duration::from_nanos(190),
+                stddev: Duration::from_nanos(7),
+            };
+            timings.data[0].part_1_stats = Some(stats.clone());
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1_stats, Some(stats));
+            assert_eq!(parsed.data[0].part_2_stats, None);
+        }
+
+        #[test]
+        fn roundtrips_memory() {
+            let mut timings = get_mock_timings();
+            let memory = MemoryStats {
+                peak_bytes: 1024,
+                total_bytes: 8192,
+                allocations: 5,
+            };
+            timings.data[1].part_2_memory = Some(memory);
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[1].part_2_memory, Some(memory));
+            assert_eq!(parsed.data[1].part_1_memory, None);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -275,9 +592,28 @@ mod tests {
         }
     }
 
+    mod durations {
+        use super::get_mock_timings;
+        use std::time::Duration;
+
+        #[test]
+        fn reads_formatted_durations() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = Some("310.0ns".into());
+            timings.data[0].part_2 = Some("1.5s".into());
+
+            let timing = &timings.data[0];
+            assert_eq!(timing.parse_duration(), Some(Duration::from_nanos(310)));
+            assert_eq!(timing.part_duration(1), Some(Duration::from_millis(10)));
+            assert_eq!(timing.part_duration(2), Some(Duration::from_millis(1500)));
+            assert_eq!(timings.data[1].parse_duration(), None);
+            assert_eq!(timings.data[2].part_duration(2), None);
+        }
+    }
+
     mod is_day_complete {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -285,48 +621,66 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(puzzle!(2023, 1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(2023, 1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(2023, 1)), false);
         }
     }
 
     mod merge {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -337,18 +691,24 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
+                    puzzle: puzzle!(2023, 3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].puzzle, puzzle!(2023, 1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2023, 2));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2023, 3));
+            assert_eq!(merged.data[3].puzzle, puzzle!(2023, 4));
         }
 
         #[test]
@@ -357,19 +717,50 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2023, 2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].puzzle, puzzle!(2023, 1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2023, 2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2023, 4));
+        }
+
+        #[test]
+        fn handles_timings_of_other_years() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![Timing {
+                    puzzle: puzzle!(2022, 2),
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    total_nanos: 0_f64,
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].puzzle, puzzle!(2022, 2));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2023, 2));
         }
 
         #[test]
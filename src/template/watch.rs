@@ -0,0 +1,107 @@
+/// Detects changes to the files of a puzzle by polling their modification times.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::PuzzleId;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Modification times of the watched files of a puzzle.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Snapshot {
+    pub fn new(puzzle: PuzzleId) -> Self {
+        Snapshot(
+            watched_paths(puzzle)
+                .into_iter()
+                .map(|path| {
+                    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                    (path, modified)
+                })
+                .collect(),
+        )
+    }
+
+    /// Block until a watched file of `puzzle` is changed, added or removed.
+    pub fn wait_for_change(&self, puzzle: PuzzleId) {
+        while Snapshot::new(puzzle) == *self {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Data folders whose files are watched. Answers and submissions are left out, as runs write to them.
+const WATCHED_FOLDERS: [&str; 2] = ["inputs", "examples"];
+
+/// The solution of the puzzle, `src/lib.rs` and the inputs and examples of the day.
+fn watched_paths(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let mut paths = vec![puzzle.bin_path(), PathBuf::from("src").join("lib.rs")];
+
+    for folder in WATCHED_FOLDERS {
+        let folder = puzzle.data_dir(folder);
+        let mut files: Vec<PathBuf> = fs::read_dir(&folder)
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| is_puzzle_file(path, puzzle))
+            .collect();
+        files.sort();
+        paths.extend(files);
+    }
+
+    paths
+}
+
+/// Whether a data file belongs to the puzzle, e.g. `01.txt` or `01-2.txt` for day 1.
+fn is_puzzle_file(path: &Path, puzzle: PuzzleId) -> bool {
+    let day = puzzle.day.to_string();
+
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| {
+            stem == day
+                || stem
+                    .strip_prefix(&day)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_puzzle_file, watched_paths};
+    use crate::puzzle;
+    use std::path::Path;
+
+    #[test]
+    fn matches_puzzle_files() {
+        let is_file = |path: &str| is_puzzle_file(Path::new(path), puzzle!(2023, 1));
+
+        assert!(is_file("data/2023/inputs/01.txt"));
+        assert!(is_file("data/2023/examples/01-2.txt"));
+        assert!(is_file("data/2023/answers/01.json"));
+        assert!(!is_file("data/2023/inputs/10.txt"));
+        assert!(!is_file("data/2023/inputs/011.txt"));
+        assert!(!is_file("data/2023/inputs/.keep"));
+    }
+
+    #[test]
+    fn ignores_answers_and_submissions() {
+        let puzzle = puzzle!(2023, 1);
+        let paths = watched_paths(puzzle);
+
+        assert!(paths.contains(&puzzle.bin_path()));
+        assert!(paths.contains(&puzzle.data_path("examples", "txt")));
+        assert!(!paths
+            .iter()
+            .any(|path| path.starts_with(puzzle.data_dir("answers"))
+                || path.starts_with(puzzle.data_dir("submissions"))));
+    }
+}